@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+pub(crate) const PROBE_BYTES: usize = 64 * 1024;
+
+/// Reads `(width, height)` from the header of a PNG or JPEG image.
+pub(crate) fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.starts_with(PNG_SIGNATURE) {
+        png_dimensions(bytes)
+    } else if bytes.starts_with(&[0xFF, 0xD8]) {
+        jpeg_dimensions(bytes)
+    } else {
+        None
+    }
+}
+
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+    let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+    Some((width, height))
+}
+
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut i = 2;
+    loop {
+        if *bytes.get(i)? != 0xFF {
+            return None;
+        }
+        while *bytes.get(i)? == 0xFF {
+            i += 1;
+        }
+        let marker = *bytes.get(i)?;
+        i += 1;
+
+        match marker {
+            0x01 | 0xD0..=0xD8 => continue,
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let height = u16::from_be_bytes([*bytes.get(i + 3)?, *bytes.get(i + 4)?]);
+                let width = u16::from_be_bytes([*bytes.get(i + 5)?, *bytes.get(i + 6)?]);
+                return Some((width.into(), height.into()));
+            }
+            _ => {
+                let len = u16::from_be_bytes([*bytes.get(i)?, *bytes.get(i + 1)?]);
+                i += usize::from(len);
+            }
+        }
+    }
+}
@@ -1,34 +1,192 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-mod date_serde;
+mod dimensions;
 mod params;
+mod schedule;
 
 #[cfg(test)]
 mod test;
 
-use chrono::NaiveDate;
-use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{Datelike, Days, NaiveDate};
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, RANGE, RETRY_AFTER};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use url::Url;
 
-use crate::{Api, ApiError, StellariaError, StellariaResult};
-pub use params::{ApodParams, ApodParamsBuilder};
+use crate::date_serde;
+use crate::keys::KeyRing;
+use crate::request::{self, Reply};
+use crate::serde_util::de_trimmed_opt_string;
+use crate::{Api, ApiError, ApiToken, Clock, RateLimit, StellariaError, StellariaResult};
+pub use params::{APOD_EPOCH, ApodParams, ApodParamsBuilder, MAX_COUNT};
+pub use schedule::next_publish_time;
+
+const BASE_URL: &str = "https://api.nasa.gov/planetary/apod";
+pub(crate) const PATH: &str = "planetary/apod";
+const ENDPOINT: &str = "APOD";
+/// The most days [`ApodApi::find_missing_dates`] and [`ApodApi::stream_range`]
+/// ask for in one request, and their default chunk size.
+pub const MAX_CHUNK_DAYS: u64 = 100;
+const DISPLAY_WIDTH: usize = 80;
 
+#[derive(Debug)]
 pub struct ApodApi {
-    api_key: String,
+    keys: KeyRing,
+    base_url: Url,
     reqwest_client: reqwest::Client,
+    image_preference: ImagePref,
+    hd_hosts: Option<Vec<String>>,
+    clock: Clock,
+    check_date_window: bool,
+    validate_responses: bool,
+    skip_malformed: bool,
+    last_rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    last_skipped: Arc<Mutex<Option<SkippedEntries>>>,
+    retry_policy: RetryPolicy,
+    timeout: Option<Duration>,
+    operation_timeout: Option<Duration>,
+    chunk_days: u64,
+    #[cfg(feature = "blocking")]
+    blocking_client: Option<reqwest::blocking::Client>,
+}
+
+/// Which of an APOD's image urls to use when downloading.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImagePref {
+    /// Always the standard-resolution `url`.
+    Standard,
+    /// Only the `hdurl`; entries without one have no image.
+    Hd,
+    /// The `hdurl` when present, otherwise the standard `url`.
+    #[default]
+    HdWithFallback,
+}
+
+/// How often to retry requests that failed with 429, 500, 502, 503 or 504.
+///
+/// The delay before retry `n` is `base_delay * 2^n`, unless the failed
+/// response's `Retry-After` asks for a different wait. The default never
+/// retries.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    /// The longest wait before a retry. Backoff is clamped to it, and a
+    /// `Retry-After` asking for longer ends the retries with the failed
+    /// response's error. Unbounded when unset.
+    pub max_delay: Option<Duration>,
+}
+
+impl RetryPolicy {
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+        match self.max_delay {
+            Some(max_delay) => delay.min(max_delay),
+            None => delay,
+        }
+    }
+
+    /// The wait before retrying `err`, or `None` if it asked for longer than
+    /// `max_delay`.
+    fn delay_after(&self, err: &StellariaError, attempt: u32) -> Option<Duration> {
+        match (err.retry_after(), self.max_delay) {
+            (Some(retry_after), Some(max_delay)) if retry_after > max_delay => None,
+            (Some(retry_after), _) => Some(retry_after),
+            (None, _) => Some(self.delay(attempt)),
+        }
+    }
+
+    fn is_transient(err: &StellariaError) -> bool {
+        matches!(err.status(), Some(429 | 500 | 502 | 503 | 504))
+    }
+}
+
+/// Entries a response lost to
+/// [`ApodApi::with_malformed_entries_skipped`], see
+/// [`ApodApi::last_skipped_entries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedEntries {
+    pub count: usize,
+    /// Why the first skipped entry failed to parse.
+    pub first_error: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+/// What the [`RetryPolicy`] did during one call, see
+/// [`ApodApi::get_with_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RetryStats {
+    /// Requests sent, counting the first one.
+    pub attempts: u32,
+    pub retries: u32,
+    /// The HTTP status of the most recent failed attempt, if any.
+    pub last_status: Option<u16>,
+}
+
+/// The kind of media an APOD entry links to.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaType {
+    Image,
+    Video,
+    /// Any media type NASA adds in the future; serialized as `"other"`.
+    #[serde(other)]
+    Other,
+}
+
+impl std::fmt::Display for MediaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MediaType::Image => "image",
+            MediaType::Video => "video",
+            MediaType::Other => "other",
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ApodResponse {
+    /// Trimmed of the newlines NASA often wraps it in.
+    #[serde(default, deserialize_with = "de_trimmed_opt_string")]
     pub copyright: Option<String>,
+    #[serde(deserialize_with = "date_serde::deserialize_date")]
     pub date: NaiveDate,
     pub explanation: String,
     pub hdurl: Option<Url>,
-    pub media_type: String,
+    pub media_type: MediaType,
     pub service_version: String,
+    pub thumbnail_url: Option<Url>,
+    pub title: String,
+    pub url: Url,
+}
+
+/// The fields of an [`ApodResponse`] a list view needs, without the explanation.
+#[derive(Debug, Clone)]
+pub struct ApodSummary {
+    pub date: NaiveDate,
     pub title: String,
+    pub media_type: MediaType,
     pub url: Url,
+    pub thumbnail_url: Option<Url>,
+}
+
+impl From<ApodResponse> for ApodSummary {
+    fn from(response: ApodResponse) -> Self {
+        Self {
+            date: response.date,
+            title: response.title,
+            media_type: response.media_type,
+            url: response.url,
+            thumbnail_url: response.thumbnail_url,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Error)]
@@ -37,6 +195,29 @@ pub struct ApodApiError {
     code: u16,
     msg: String,
     service_version: String,
+    #[serde(skip)]
+    retry_after: Option<Duration>,
+}
+
+impl ApodApiError {
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// How long NASA asked to wait, from the `Retry-After` of a 429 or 503.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// The APOD service version NASA reported, or `"unknown"` when the error
+    /// body wasn't APOD's JSON (e.g. a gateway error).
+    pub fn service_version(&self) -> &str {
+        &self.service_version
+    }
 }
 
 #[derive(Deserialize, Debug, Error)]
@@ -46,6 +227,34 @@ pub enum ApodError {
     ApodApiError(#[from] ApodApiError),
     #[error("invalid parameters: {0}")]
     ApodParamsError(String),
+    #[error("APOD for {date} is outside the requested window {start_date} to {end_date}")]
+    DateOutOfWindow {
+        date: NaiveDate,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    },
+    #[serde(skip)]
+    #[error("invalid http request: {0}")]
+    Network(#[source] reqwest::Error),
+    #[serde(skip)]
+    #[error("error in parsing json: {0}")]
+    Json(#[source] serde_json::Error),
+    #[error("response body ended unexpectedly after {bytes_read} bytes")]
+    TruncatedResponse { bytes_read: usize },
+    #[error("APOD for {date} is a video and can't be downloaded as an image")]
+    VideoNotDownloadable { date: NaiveDate },
+    #[error("APOD for {date} has no image url for the image preference")]
+    NoImageUrl { date: NaiveDate },
+    #[error("APOD for {date} is a video without a thumbnail_url; request thumbs")]
+    MissingThumbnail { date: NaiveDate },
+    #[error("expected exactly one APOD, got {count}")]
+    NotSingle { count: usize },
+    #[error("APOD response has no usable {}", missing.join(", "))]
+    IncompleteResponse { missing: Vec<String> },
+    /// Any failure without an APOD-specific variant.
+    #[serde(skip)]
+    #[error(transparent)]
+    Other(Box<StellariaError>),
 }
 
 #[derive(Deserialize)]
@@ -53,65 +262,1066 @@ pub enum ApodError {
 enum ApiResponse {
     Error(ApodApiError),
     One(Box<ApodResponse>),
-    Many(Vec<ApodResponse>),
+    // Entries are parsed one by one so a single malformed entry (e.g. a
+    // missing `url`) can be skipped, see `ApodApi::with_malformed_entries_skipped`.
+    Many(Vec<serde_json::Value>),
 }
 
-impl ApiResponse {
-    fn parse(self) -> Result<Vec<ApodResponse>, ApodError> {
+impl ApodResponse {
+    pub fn is_image(&self) -> bool {
+        self.media_type == MediaType::Image
+    }
+
+    pub fn is_video(&self) -> bool {
+        self.media_type == MediaType::Video
+    }
+
+    pub fn image_url(&self, pref: ImagePref) -> Option<&Url> {
+        match pref {
+            ImagePref::Standard => Some(&self.url),
+            ImagePref::Hd => self.hdurl.as_ref(),
+            ImagePref::HdWithFallback => self.hdurl.as_ref().or(Some(&self.url)),
+        }
+    }
+
+    /// Probes the image's `(width, height)` by downloading only its header.
+    ///
+    /// Returns `None` for videos and for formats other than PNG and JPEG.
+    pub async fn dimensions(
+        &self,
+        client: &reqwest::Client,
+    ) -> StellariaResult<Option<(u32, u32)>> {
+        if !self.is_image() {
+            return Ok(None);
+        }
+
+        let mut resp = client
+            .get(self.url.clone())
+            .header(RANGE, format!("bytes=0-{}", dimensions::PROBE_BYTES - 1))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = resp.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if let Some(dimensions) = dimensions::image_dimensions(&bytes) {
+                return Ok(Some(dimensions));
+            }
+            if bytes.len() >= dimensions::PROBE_BYTES {
+                break;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Downloads the image at [`ApodResponse::image_url`] for `pref`. Use
+    /// [`ApodApi::download`] to honor an [`ApodApi`]'s HD host allowlist too.
+    ///
+    /// Fails with [`ApodError::VideoNotDownloadable`] for videos and with
+    /// [`ApodError::NoImageUrl`] when `pref` leaves no url, i.e.
+    /// [`ImagePref::Hd`] without an `hdurl`.
+    pub async fn download(
+        &self,
+        client: &reqwest::Client,
+        pref: ImagePref,
+    ) -> StellariaResult<Bytes> {
+        self.download_from(client, self.image_url(pref)).await
+    }
+
+    async fn download_from(
+        &self,
+        client: &reqwest::Client,
+        url: Option<&Url>,
+    ) -> StellariaResult<Bytes> {
+        if self.is_video() {
+            return Err(ApodError::VideoNotDownloadable { date: self.date }.into());
+        }
+        let Some(url) = url else {
+            return Err(ApodError::NoImageUrl { date: self.date }.into());
+        };
+
+        get_bytes(client, url).await
+    }
+
+    /// The required fields that parsed but hold nothing usable: a blank
+    /// title, a url without an http(s) host, or a date before [`APOD_EPOCH`].
+    fn missing_fields(&self) -> Vec<String> {
+        let mut missing = Vec::new();
+        if self.title.trim().is_empty() {
+            missing.push("title".to_string());
+        }
+        if !matches!(self.url.scheme(), "http" | "https") || self.url.host().is_none() {
+            missing.push("url".to_string());
+        }
+        if self.date < APOD_EPOCH {
+            missing.push("date".to_string());
+        }
+        missing
+    }
+
+    /// Like [`ApodResponse::download`], but writes the image to `path`.
+    pub async fn download_to(
+        &self,
+        client: &reqwest::Client,
+        pref: ImagePref,
+        path: &Path,
+    ) -> StellariaResult<()> {
+        let bytes = self.download(client, pref).await?;
+        tokio::fs::write(path, &bytes).await?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ApodResponse {
+    /// Renders a terminal-friendly block: a `date — title (media type)`
+    /// heading, the explanation wrapped to 80 columns, then the url.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} — {} ({})", self.date, self.title, self.media_type)?;
+        writeln!(f)?;
+
+        let mut line_len = 0;
+        for word in self.explanation.split_whitespace() {
+            if line_len > 0 && line_len + 1 + word.chars().count() > DISPLAY_WIDTH {
+                writeln!(f)?;
+                line_len = 0;
+            }
+            if line_len > 0 {
+                write!(f, " ")?;
+                line_len += 1;
+            }
+            write!(f, "{word}")?;
+            line_len += word.chars().count();
+        }
+        if line_len > 0 {
+            writeln!(f)?;
+        }
+
+        writeln!(f)?;
+        write!(f, "{}", self.url)
+    }
+}
+
+impl ApodError {
+    pub fn status(&self) -> Option<u16> {
         match self {
-            ApiResponse::Error(error) => Err(ApodError::ApodApiError(error)),
-            ApiResponse::One(response) => Ok(vec![*response]),
-            ApiResponse::Many(responses) => Ok(responses),
+            ApodError::ApodApiError(err) => Some(err.code),
+            ApodError::Network(err) => err.status().map(|status| status.as_u16()),
+            ApodError::ApodParamsError(_)
+            | ApodError::DateOutOfWindow { .. }
+            | ApodError::Json(_)
+            | ApodError::TruncatedResponse { .. }
+            | ApodError::VideoNotDownloadable { .. }
+            | ApodError::NoImageUrl { .. }
+            | ApodError::MissingThumbnail { .. }
+            | ApodError::NotSingle { .. }
+            | ApodError::IncompleteResponse { .. } => None,
+            ApodError::Other(err) => err.status(),
         }
     }
 }
 
+impl ApiResponse {
+    /// Normalizes every shape to a `Vec`. NASA sometimes answers a one-day
+    /// `start_date`/`end_date` range with a bare object instead of a list.
+    ///
+    /// With `skip_malformed`, entries that fail to parse are left out unless
+    /// none parse at all, and reported next to the rest; otherwise the first
+    /// one fails the response.
+    fn parse(
+        self,
+        skip_malformed: bool,
+    ) -> StellariaResult<(Vec<ApodResponse>, Option<SkippedEntries>)> {
+        let entries = match self {
+            ApiResponse::Error(error) => return Err(ApodError::ApodApiError(error).into()),
+            ApiResponse::One(response) => return Ok((vec![*response], None)),
+            ApiResponse::Many(entries) => entries,
+        };
+
+        let mut responses = Vec::with_capacity(entries.len());
+        let mut skipped = 0;
+        let mut first_error = None;
+        for entry in entries {
+            match serde_json::from_value(entry) {
+                Ok(response) => responses.push(response),
+                Err(err) if !skip_malformed => return Err(StellariaError::JsonError(err)),
+                Err(err) => {
+                    skipped += 1;
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) if responses.is_empty() => Err(StellariaError::JsonError(err)),
+            Some(err) => Ok((
+                responses,
+                Some(SkippedEntries {
+                    count: skipped,
+                    first_error: err.to_string(),
+                }),
+            )),
+            None => Ok((responses, None)),
+        }
+    }
+}
+
+/// Writes `responses` as JSON Lines, one response per line.
+pub fn to_jsonl<W: Write>(responses: &[ApodResponse], mut writer: W) -> io::Result<()> {
+    for response in responses {
+        serde_json::to_writer(&mut writer, response)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+async fn fetch_preview(
+    client: &reqwest::Client,
+    response: &ApodResponse,
+    url: Option<&Url>,
+) -> (NaiveDate, StellariaResult<Bytes>) {
+    let result = match url {
+        Some(url) => get_bytes(client, url).await,
+        None if response.is_video() => Err(ApodError::MissingThumbnail {
+            date: response.date,
+        }
+        .into()),
+        None => Err(ApodError::NoImageUrl {
+            date: response.date,
+        }
+        .into()),
+    };
+    (response.date, result)
+}
+
+/// The lowercased extension of the url's file name, `jpg` when it has none.
+fn image_extension(url: &Url) -> String {
+    url.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|extension| extension.to_str())
+        .map_or_else(|| "jpg".to_string(), str::to_ascii_lowercase)
+}
+
+async fn get_bytes(client: &reqwest::Client, url: &Url) -> StellariaResult<Bytes> {
+    let bytes = client
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    Ok(bytes)
+}
+
+/// Flattens the results of several queries into one chronological list with
+/// a single entry per date; the first occurrence of a date wins.
+pub fn merge_sorted_unique(batches: Vec<Vec<ApodResponse>>) -> Vec<ApodResponse> {
+    let mut by_date = BTreeMap::new();
+    for response in batches.into_iter().flatten() {
+        by_date.entry(response.date).or_insert(response);
+    }
+    by_date.into_values().collect()
+}
+
+/// Picks a day in `APOD_EPOCH..=today` from `seed`.
+///
+/// Uses SplitMix64 rather than a `rand` generator so a seed keeps picking
+/// the same date across dependency upgrades.
+fn surprise_date(seed: u64, today: NaiveDate) -> NaiveDate {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+
+    let days = count_days_until(APOD_EPOCH, today, today).unwrap_or(1) as u128;
+    let offset = ((u128::from(z) * days) >> 64) as u64;
+    APOD_EPOCH + Days::new(offset)
+}
+
+/// Counts the days in `[start, end]` that can have an APOD, after clamping
+/// the range to [`APOD_EPOCH`] through today. Makes no request.
+pub fn count_days(start: NaiveDate, end: NaiveDate) -> StellariaResult<usize> {
+    count_days_until(start, end, Clock::system().today())
+}
+
+fn count_days_until(start: NaiveDate, end: NaiveDate, today: NaiveDate) -> StellariaResult<usize> {
+    if start > end {
+        return Err(ApodError::ApodParamsError(
+            "Start date cannot be greater than end date".to_string(),
+        )
+        .into());
+    }
+
+    let start = start.max(APOD_EPOCH);
+    let end = end.min(today);
+    Ok(usize::try_from((end - start).num_days() + 1).unwrap_or(0))
+}
+
+/// Parses a user-supplied date string such as `2024-12-12` or `2024/12/12`.
+pub fn parse_date(input: &str) -> StellariaResult<NaiveDate> {
+    date_serde::parse_user_date(input).ok_or_else(|| {
+        ApodError::ApodParamsError(format!(
+            "invalid date {input:?}, expected YYYY-MM-DD, YYYY/MM/DD or YYYYMMDD"
+        ))
+        .into()
+    })
+}
+
 impl ApodApi {
-    pub fn new(api_key: String, reqwest_client: reqwest::Client) -> Self {
+    pub fn new(api_key: impl Into<ApiToken>, reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default APOD base url is valid");
+        Self::with_key_ring(KeyRing::new(api_key.into(), []), reqwest_client, base_url)
+    }
+
+    /// Like [`ApodApi::new`], but sends requests to `base_url` instead of
+    /// NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(
+        api_key: impl Into<ApiToken>,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        Ok(Self::with_key_ring(
+            KeyRing::new(api_key.into(), []),
+            reqwest_client,
+            base_url,
+        ))
+    }
+
+    pub(crate) fn with_key_ring(
+        keys: KeyRing,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> Self {
         Self {
-            api_key,
+            keys,
+            base_url,
             reqwest_client,
+            image_preference: ImagePref::default(),
+            hd_hosts: None,
+            clock: Clock::system(),
+            check_date_window: false,
+            validate_responses: false,
+            skip_malformed: false,
+            last_rate_limit: Arc::default(),
+            last_skipped: Arc::default(),
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+            operation_timeout: None,
+            chunk_days: MAX_CHUNK_DAYS,
+            #[cfg(feature = "blocking")]
+            blocking_client: None,
         }
     }
-}
 
-impl Api for ApodApi {
-    type Params = ApodParams;
-    type Response = Vec<ApodResponse>;
+    /// Extra api_keys to rotate to when the active key gets a 429. The next
+    /// key is the one with the most remaining quota, as last reported by NASA.
+    pub fn with_fallback_keys<T: Into<ApiToken>>(
+        mut self,
+        fallback_keys: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let fallback_keys = fallback_keys.into_iter().map(Into::into);
+        self.keys = KeyRing::new(self.keys.primary().clone(), fallback_keys);
+        self
+    }
 
-    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
-        let url = format!(
-            "https://api.nasa.gov/planetary/apod?api_key={}",
-            self.api_key
-        );
-
-        let resp = self
-            .reqwest_client
-            .get(&url)
-            .query(&params)
-            .send()
+    /// When enabled, range queries fail with [`ApodError::DateOutOfWindow`]
+    /// if NASA returns an entry outside the requested dates. Off by default.
+    pub fn with_date_window_check(mut self, check_date_window: bool) -> Self {
+        self.check_date_window = check_date_window;
+        self
+    }
+
+    /// When enabled, responses with a blank title, an unusable url or an
+    /// impossible date fail with [`ApodError::IncompleteResponse`] instead
+    /// of reaching the caller. Off by default.
+    pub fn with_response_validation(mut self, validate_responses: bool) -> Self {
+        self.validate_responses = validate_responses;
+        self
+    }
+
+    /// When enabled, entries of a range or count response that fail to
+    /// parse, e.g. one missing its `url`, are left out instead of failing the
+    /// whole response, and reported by
+    /// [`last_skipped_entries`](Self::last_skipped_entries). A response with
+    /// no parseable entry still fails. Off by default.
+    pub fn with_malformed_entries_skipped(mut self, skip_malformed: bool) -> Self {
+        self.skip_malformed = skip_malformed;
+        self
+    }
+
+    /// Retries transient failures with exponential backoff.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Fails each request that takes longer than `timeout` with an error for
+    /// which [`StellariaError::is_timeout`] is true. Unset by default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails a call with [`StellariaError::Timeout`] once it has taken
+    /// longer than `operation_timeout`, retries and backoff included, while
+    /// [`with_timeout`](Self::with_timeout) bounds each attempt. Unset by
+    /// default.
+    pub fn with_operation_timeout(mut self, operation_timeout: Duration) -> Self {
+        self.operation_timeout = Some(operation_timeout);
+        self
+    }
+
+    /// How many days the range helpers fetch per request, from 1 to
+    /// [`MAX_CHUNK_DAYS`]. Smaller chunks mean more but quicker requests.
+    pub fn with_chunk_days(mut self, chunk_days: u64) -> StellariaResult<Self> {
+        if !(1..=MAX_CHUNK_DAYS).contains(&chunk_days) {
+            return Err(StellariaError::ConfigError(format!(
+                "chunk_days must be between 1 and {MAX_CHUNK_DAYS}, got {chunk_days}"
+            )));
+        }
+        self.chunk_days = chunk_days;
+        Ok(self)
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// A params builder whose "today" comes from this API's clock.
+    pub fn params(&self) -> ApodParamsBuilder {
+        ApodParams::builder().today(self.clock.today())
+    }
+
+    pub fn with_image_preference(mut self, image_preference: ImagePref) -> Self {
+        self.image_preference = image_preference;
+        self
+    }
+
+    pub fn image_preference(&self) -> ImagePref {
+        self.image_preference
+    }
+
+    /// Only trusts `hdurl`s on these hosts, e.g. `apod.nasa.gov`. Other HD
+    /// hosts often block hotlinking, so [`ApodApi::image_url`], and with it
+    /// [`ApodApi::download`], treats such entries as having no `hdurl` and
+    /// falls back to `url`.
+    pub fn with_hd_host_allowlist<T: Into<String>>(
+        mut self,
+        hosts: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let hosts = hosts
+            .into_iter()
+            .map(|host| host.into().to_ascii_lowercase());
+        self.hd_hosts = Some(hosts.collect());
+        self
+    }
+
+    /// The `X-RateLimit-*` quota reported by the most recent response, or
+    /// `None` before the first request or when the headers were missing.
+    pub fn last_rate_limit(&self) -> Option<RateLimit> {
+        *self
+            .last_rate_limit
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// What the most recent response lost to
+    /// [`with_malformed_entries_skipped`](Self::with_malformed_entries_skipped),
+    /// or `None` when it parsed in full.
+    pub fn last_skipped_entries(&self) -> Option<SkippedEntries> {
+        self.last_skipped
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Picks the image url of `response` according to the configured
+    /// [`ImagePref`] and HD host allowlist.
+    pub fn image_url<'a>(&self, response: &'a ApodResponse) -> Option<&'a Url> {
+        let hd_allowed = match (&self.hd_hosts, &response.hdurl) {
+            (Some(hosts), Some(hdurl)) => hdurl
+                .host_str()
+                .is_some_and(|host| hosts.iter().any(|allowed| allowed == host)),
+            _ => true,
+        };
+
+        match self.image_preference {
+            ImagePref::Hd if !hd_allowed => None,
+            ImagePref::HdWithFallback if !hd_allowed => Some(&response.url),
+            pref => response.image_url(pref),
+        }
+    }
+
+    /// Like [`ApodResponse::download`], but picks the url with
+    /// [`ApodApi::image_url`] and downloads it with this api's client.
+    pub async fn download(&self, response: &ApodResponse) -> StellariaResult<Bytes> {
+        response
+            .download_from(&self.reqwest_client, self.image_url(response))
             .await
-            .map_err(StellariaError::RequestError)?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            let text = resp.text().await.map_err(StellariaError::RequestError)?;
-            let truncated = text.chars().take(1024).collect::<String>();
-            return Err(ApodError::ApodApiError(ApodApiError {
-                code: status.as_u16(),
-                msg: truncated,
-                service_version: "unknown".into(),
+    }
+
+    /// Like [`ApodApi::download`], but writes the image to `path`.
+    pub async fn download_to(&self, response: &ApodResponse, path: &Path) -> StellariaResult<()> {
+        let bytes = self.download(response).await?;
+        tokio::fs::write(path, &bytes).await?;
+        Ok(())
+    }
+
+    /// Downloads the image a gallery should show for every response, with at
+    /// most `concurrency` requests in flight and in the order of `responses`:
+    /// the thumbnail for videos, the [`ApodApi::image_url`] otherwise.
+    ///
+    /// Videos need a `thumbnail_url`, so query them with
+    /// [`ApodParamsBuilder::thumbs`]; those without one fail with
+    /// [`ApodError::MissingThumbnail`].
+    pub async fn fetch_previews(
+        &self,
+        responses: &[ApodResponse],
+        concurrency: usize,
+    ) -> Vec<(NaiveDate, StellariaResult<Bytes>)> {
+        // The futures are collected up front so no closure is left inside the
+        // stream: one there trips a higher-ranked lifetime error that makes the
+        // returned future `!Send`.
+        let previews: Vec<_> = responses
+            .iter()
+            .map(|response| {
+                let url = match response.media_type {
+                    MediaType::Video => response.thumbnail_url.as_ref(),
+                    MediaType::Image | MediaType::Other => self.image_url(response),
+                };
+                fetch_preview(&self.reqwest_client, response, url)
             })
+            .collect();
+        stream::iter(previews)
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Saves the APOD for `date` into `dir`, creating it if needed: the
+    /// metadata as `{date}.json` and the image from [`ApodApi::image_url`]
+    /// as `{date}.{ext}`. Videos get a `{date}.url` shortcut to the video
+    /// instead of an image. Returns the paths written.
+    pub async fn archive_to(
+        &self,
+        date: NaiveDate,
+        dir: &Path,
+        client: &reqwest::Client,
+    ) -> StellariaResult<Vec<PathBuf>> {
+        let response = self.get_one(self.params().date(date).build()?).await?;
+        tokio::fs::create_dir_all(dir).await?;
+
+        let metadata = dir.join(format!("{date}.json"));
+        tokio::fs::write(&metadata, serde_json::to_vec_pretty(&response)?).await?;
+
+        let media = if response.is_video() {
+            let shortcut = dir.join(format!("{date}.url"));
+            let contents = format!("[InternetShortcut]\nURL={}\n", response.url);
+            tokio::fs::write(&shortcut, contents).await?;
+            shortcut
+        } else {
+            let url = self.image_url(&response).unwrap_or(&response.url);
+            let image = dir.join(format!("{date}.{}", image_extension(url)));
+            tokio::fs::write(&image, get_bytes(client, url).await?).await?;
+            image
+        };
+
+        Ok(vec![metadata, media])
+    }
+
+    /// Fetches today's APOD, falling back to yesterday's when today's picture
+    /// has not been published yet.
+    pub async fn latest_available(&self) -> StellariaResult<ApodResponse> {
+        let today = self.clock.today();
+        if let Some(response) = self.get_on(today).await? {
+            return Ok(response);
+        }
+
+        let yesterday = today.pred_opt().expect("today has a previous day");
+        self.get_on(yesterday).await?.ok_or_else(|| {
+            ApodApiError {
+                code: 404,
+                msg: format!("no APOD available for {today} or {yesterday}"),
+                service_version: "unknown".into(),
+                retry_after: None,
+            }
+            .into()
+        })
+    }
+
+    /// The date of the most recent APOD, for cheap "is there a new one?"
+    /// polling.
+    ///
+    /// Sends a GET without a `date` and lets NASA pick its latest entry; a
+    /// HEAD response has no body and no documented header naming the date.
+    /// Unlike [`ApodApi::params`] it can't ask for a local "today" that
+    /// Eastern time hasn't reached yet.
+    pub async fn latest_date(&self) -> StellariaResult<NaiveDate> {
+        let mut responses = self.get(ApodParams::default()).await?;
+        match responses.len() {
+            1 => Ok(responses.remove(0).date),
+            count => Err(ApodError::NotSingle { count }.into()),
+        }
+    }
+
+    /// Like [`Api::get`] for params naming a single day (a `date`, or none for
+    /// today), returning that day's response on its own.
+    ///
+    /// Count and range params are rejected before any request is sent; a
+    /// response with other than exactly one entry fails with
+    /// [`ApodError::NotSingle`].
+    pub async fn get_one(&self, params: ApodParams) -> StellariaResult<ApodResponse> {
+        if params.count.is_some() || params.start_date.is_some() || params.end_date.is_some() {
+            return Err(ApodError::ApodParamsError(
+                "get_one needs a single date, not a count or date_range".to_string(),
+            )
             .into());
         }
 
-        let apod_resp = resp
-            .json::<ApiResponse>()
+        let mut responses = self.get(params).await?;
+        match responses.len() {
+            1 => Ok(responses.remove(0)),
+            count => Err(ApodError::NotSingle { count }.into()),
+        }
+    }
+
+    /// Fetches the APOD of a uniformly random day between [`APOD_EPOCH`] and
+    /// today. The day is picked locally, so the same `seed` on the same day
+    /// always picks the same date; without one the seed comes from the clock.
+    pub async fn surprise(&self, seed: Option<u64>) -> StellariaResult<ApodResponse> {
+        let seed = seed.unwrap_or_else(|| self.clock.now().timestamp_micros() as u64);
+        let date = surprise_date(seed, self.clock.today());
+        self.get_on(date).await?.ok_or_else(|| {
+            ApodApiError {
+                code: 404,
+                msg: format!("no APOD available for {date}"),
+                service_version: "unknown".into(),
+                retry_after: None,
+            }
+            .into()
+        })
+    }
+
+    /// Fetches only `date` and returns its [`MediaType`], so calendars can
+    /// badge video days without loading media.
+    pub async fn media_type_on(&self, date: NaiveDate) -> StellariaResult<MediaType> {
+        let params = self.params().date(date).build()?;
+        self.get(params)
+            .await?
+            .into_iter()
+            .next()
+            .map(|response| response.media_type)
+            .ok_or_else(|| {
+                ApodApiError {
+                    code: 404,
+                    msg: format!("no APOD available for {date}"),
+                    service_version: "unknown".into(),
+                    retry_after: None,
+                }
+                .into()
+            })
+    }
+
+    /// Lists every date from [`APOD_EPOCH`] through today, oldest first.
+    ///
+    /// Computed locally without any request, so offline apps can build a date
+    /// index and fetch entries lazily.
+    pub fn fetch_all_dates(&self) -> StellariaResult<Vec<NaiveDate>> {
+        let today = self.clock.today();
+        Ok(APOD_EPOCH
+            .iter_days()
+            .take_while(|date| *date <= today)
+            .collect())
+    }
+
+    /// Fetches the APOD published on `month`/`day` of every year from
+    /// `from_year` until now, keyed by year. Years without a picture are skipped.
+    pub async fn anniversaries(
+        &self,
+        month: u32,
+        day: u32,
+        from_year: i32,
+    ) -> StellariaResult<BTreeMap<i32, ApodResponse>> {
+        if NaiveDate::from_ymd_opt(2000, month, day).is_none() {
+            return Err(
+                ApodError::ApodParamsError(format!("invalid month/day: {month}/{day}")).into(),
+            );
+        }
+
+        let today = self.clock.today();
+        let mut anniversaries = BTreeMap::new();
+        for year in from_year.max(APOD_EPOCH.year())..=today.year() {
+            let Some(date) = NaiveDate::from_ymd_opt(year, month, day) else {
+                continue;
+            };
+            if date < APOD_EPOCH || date > today {
+                continue;
+            }
+            if let Some(response) = self.get_on(date).await? {
+                anniversaries.insert(year, response);
+            }
+        }
+
+        Ok(anniversaries)
+    }
+
+    /// Returns the dates in `[start, end]` for which NASA published no picture.
+    ///
+    /// Large ranges are fetched in chunks of [`ApodApi::with_chunk_days`] days.
+    pub async fn find_missing_dates(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> StellariaResult<Vec<NaiveDate>> {
+        let mut missing = Vec::new();
+        let mut chunk_start = start;
+        while chunk_start <= end {
+            let chunk_end = (chunk_start + Days::new(self.chunk_days - 1)).min(end);
+            let params = self.params().date_range(chunk_start, chunk_end).build()?;
+            let published: HashSet<NaiveDate> = self
+                .get(params)
+                .await?
+                .into_iter()
+                .map(|response| response.date)
+                .collect();
+
+            missing.extend(
+                chunk_start
+                    .iter_days()
+                    .take_while(|date| *date <= chunk_end)
+                    .filter(|date| !published.contains(date)),
+            );
+            chunk_start = chunk_end + Days::new(1);
+        }
+
+        Ok(missing)
+    }
+
+    /// Streams every APOD in `[start, end]` in date order, fetching
+    /// [`ApodApi::with_chunk_days`] days at a time and only when the previous
+    /// chunk is used up.
+    ///
+    /// A failed chunk is yielded as a single `Err` and ends the stream, so
+    /// the items before it are always a gap-free prefix of the range.
+    pub fn stream_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> impl Stream<Item = StellariaResult<ApodResponse>> + '_ {
+        stream::unfold(Some(start), move |chunk_start| async move {
+            let chunk_start = chunk_start?;
+            let chunk_end = (chunk_start + Days::new(self.chunk_days - 1)).min(end);
+            let chunk = match self.params().date_range(chunk_start, chunk_end).build() {
+                Ok(params) => self.get(params).await,
+                Err(err) => Err(err),
+            };
+            match chunk {
+                Ok(mut responses) => {
+                    responses.sort_by_key(|response| response.date);
+                    let next = (chunk_end < end).then(|| chunk_end + Days::new(1));
+                    Some((responses.into_iter().map(Ok).collect(), next))
+                }
+                Err(err) => Some((vec![Err(err)], None)),
+            }
+        })
+        .flat_map(stream::iter)
+    }
+
+    /// Like [`Api::get`], but fails with a flat [`ApodError`] instead of the
+    /// crate-wide [`StellariaError`].
+    pub async fn get_apod(&self, params: ApodParams) -> Result<Vec<ApodResponse>, ApodError> {
+        self.get(params).await.map_err(ApodError::from)
+    }
+
+    /// Like [`Api::get`], but keeps only the fields in [`ApodSummary`].
+    pub async fn get_summaries(&self, params: ApodParams) -> StellariaResult<Vec<ApodSummary>> {
+        let responses = self.get(params).await?;
+        Ok(responses.into_iter().map(ApodSummary::from).collect())
+    }
+
+    /// Like [`Api::get`], but also returns the HTTP response headers with any
+    /// echoed api_key scrubbed.
+    pub async fn get_with_headers(
+        &self,
+        params: ApodParams,
+    ) -> StellariaResult<(Vec<ApodResponse>, HeaderMap)> {
+        self.send_bounded(&params, &mut RetryStats::default())
+            .await
+            .map_err(|err| err.with_context(ENDPOINT, params.describe()))
+    }
+
+    /// Like [`Api::get`], but also reports how many attempts the
+    /// [`RetryPolicy`] needed.
+    pub async fn get_with_stats(
+        &self,
+        params: ApodParams,
+    ) -> StellariaResult<(Vec<ApodResponse>, RetryStats)> {
+        let mut stats = RetryStats::default();
+        let (responses, _) = self
+            .send_bounded(&params, &mut stats)
             .await
-            .map_err(StellariaError::RequestError)?;
+            .map_err(|err| err.with_context(ENDPOINT, params.describe()))?;
+        Ok((responses, stats))
+    }
+
+    /// Like [`send_retrying`](Self::send_retrying), but gives up once the
+    /// operation timeout runs out.
+    async fn send_bounded(
+        &self,
+        params: &ApodParams,
+        stats: &mut RetryStats,
+    ) -> StellariaResult<(Vec<ApodResponse>, HeaderMap)> {
+        match self.operation_timeout {
+            Some(limit) => tokio::time::timeout(limit, self.send_retrying(params, stats))
+                .await
+                .unwrap_or(Err(StellariaError::Timeout(limit))),
+            None => self.send_retrying(params, stats).await,
+        }
+    }
+
+    /// Sends `params`, retrying transient failures as the [`RetryPolicy`]
+    /// allows and recording each attempt in `stats`.
+    async fn send_retrying(
+        &self,
+        params: &ApodParams,
+        stats: &mut RetryStats,
+    ) -> StellariaResult<(Vec<ApodResponse>, HeaderMap)> {
+        loop {
+            stats.attempts += 1;
+            let result = self.send_rotating(params).await;
+            if let Err(err) = &result {
+                stats.last_status = err.status().or(stats.last_status);
+            }
+            let delay = match &result {
+                Err(err)
+                    if stats.retries < self.retry_policy.max_retries
+                        && RetryPolicy::is_transient(err) =>
+                {
+                    self.retry_policy.delay_after(err, stats.retries)
+                }
+                _ => None,
+            };
+            match delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    stats.retries += 1;
+                }
+                None => return result,
+            }
+        }
+    }
+
+    /// Sends `params`, rotating to a fallback key whenever one is rate limited.
+    async fn send_rotating(
+        &self,
+        params: &ApodParams,
+    ) -> StellariaResult<(Vec<ApodResponse>, HeaderMap)> {
+        request::rotate_keys(&self.keys, |key_index| async move {
+            let mut request = self
+                .reqwest_client
+                .get(self.base_url.clone())
+                .query(&[("api_key", self.keys.get(key_index).expose())])
+                .query(params);
+            if let Some(timeout) = self.timeout {
+                request = request.timeout(timeout);
+            }
+            let reply = request::fetch(ENDPOINT, request).await?;
+            self.handle_response(key_index, params, &reply)
+        })
+        .await
+    }
+
+    /// Everything after the body is read, shared by the async and blocking
+    /// paths so the two can't drift apart.
+    fn handle_response(
+        &self,
+        key_index: usize,
+        params: &ApodParams,
+        reply: &Reply,
+    ) -> StellariaResult<(Vec<ApodResponse>, HeaderMap)> {
+        let rate_limit = RateLimit::from_headers(&reply.headers);
+        self.keys.record(key_index, rate_limit);
+        *self
+            .last_rate_limit
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) =
+            (rate_limit.limit.is_some() || rate_limit.remaining.is_some()).then_some(rate_limit);
+
+        if !reply.status.is_success() {
+            let mut err =
+                serde_json::from_slice::<ApodApiError>(&reply.body).unwrap_or_else(|_| {
+                    ApodApiError {
+                        code: reply.status.as_u16(),
+                        msg: request::error_message(&reply.body),
+                        service_version: "unknown".into(),
+                        retry_after: None,
+                    }
+                });
+            if matches!(reply.status.as_u16(), 429 | 503) {
+                err.retry_after = reply
+                    .headers
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| crate::parse_retry_after(value, self.clock.now()));
+            }
+            return Err(ApodError::ApodApiError(err).into());
+        }
+
+        let headers = self.scrub_headers(&reply.headers);
+        let apod_resp = request::parse_json::<ApiResponse>(&reply.body)?;
+
+        let (responses, skipped) = apod_resp.parse(self.skip_malformed)?;
+        *self
+            .last_skipped
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = skipped;
+        if self.check_date_window {
+            self.check_window(params, &responses)?;
+        }
+        if self.validate_responses
+            && let Some(missing) = responses
+                .iter()
+                .map(ApodResponse::missing_fields)
+                .find(|missing| !missing.is_empty())
+        {
+            return Err(ApodError::IncompleteResponse { missing }.into());
+        }
+
+        Ok((responses, headers))
+    }
+
+    fn check_window(
+        &self,
+        params: &ApodParams,
+        responses: &[ApodResponse],
+    ) -> Result<(), ApodError> {
+        let Some(start_date) = params.start_date else {
+            return Ok(());
+        };
+        let end_date = params.end_date.unwrap_or_else(|| self.clock.today());
+
+        match responses
+            .iter()
+            .find(|response| response.date < start_date || response.date > end_date)
+        {
+            Some(response) => Err(ApodError::DateOutOfWindow {
+                date: response.date,
+                start_date,
+                end_date,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn scrub_headers(&self, headers: &HeaderMap) -> HeaderMap {
+        let mut scrubbed = headers.clone();
+        for api_key in self.keys.iter().map(ApiToken::expose) {
+            if api_key.is_empty() {
+                continue;
+            }
+            for value in scrubbed.values_mut() {
+                if let Ok(text) = value.to_str()
+                    && text.contains(api_key)
+                {
+                    *value = HeaderValue::from_str(&text.replace(api_key, "***"))
+                        .expect("scrubbed header value is still valid");
+                }
+            }
+        }
+        scrubbed
+    }
+
+    async fn get_on(&self, date: NaiveDate) -> StellariaResult<Option<ApodResponse>> {
+        let params = self.params().date(date).build()?;
+        match self.get(params).await {
+            Ok(responses) => Ok(responses.into_iter().next()),
+            Err(err) if err.status() == Some(404) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl ApodApi {
+    /// Client for [`ApodApi::blocking_get`]. `reqwest::blocking::Client`
+    /// panics when created or dropped inside an async runtime, so it's never
+    /// made implicitly.
+    pub fn with_blocking_client(mut self, blocking_client: reqwest::blocking::Client) -> Self {
+        self.blocking_client = Some(blocking_client);
+        self
+    }
+
+    /// Like [`Api::get`], but without an async runtime. Key rotation and the
+    /// [`RetryPolicy`] behave the same; backoff sleeps the current thread.
+    /// An attempt in flight can't be cancelled, so the operation timeout only
+    /// stops retries whose wait would run past it.
+    pub fn blocking_get(&self, params: ApodParams) -> StellariaResult<Vec<ApodResponse>> {
+        let Some(blocking_client) = &self.blocking_client else {
+            return Err(StellariaError::ConfigError(
+                "blocking_get needs a blocking client, see ApodApi::with_blocking_client"
+                    .to_string(),
+            ));
+        };
+
+        let deadline = self
+            .operation_timeout
+            .map(|limit| (std::time::Instant::now() + limit, limit));
+        let mut attempt = 0;
+        loop {
+            match self.blocking_send_rotating(blocking_client, &params) {
+                Err(err)
+                    if attempt < self.retry_policy.max_retries
+                        && RetryPolicy::is_transient(&err)
+                        && let Some(delay) = self.retry_policy.delay_after(&err, attempt) =>
+                {
+                    if let Some((deadline, limit)) = deadline
+                        && std::time::Instant::now() + delay >= deadline
+                    {
+                        return Err(StellariaError::Timeout(limit)
+                            .with_context(ENDPOINT, params.describe()));
+                    }
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                }
+                result => {
+                    return result
+                        .map(|(responses, _)| responses)
+                        .map_err(|err| err.with_context(ENDPOINT, params.describe()));
+                }
+            }
+        }
+    }
+
+    fn blocking_send_rotating(
+        &self,
+        blocking_client: &reqwest::blocking::Client,
+        params: &ApodParams,
+    ) -> StellariaResult<(Vec<ApodResponse>, HeaderMap)> {
+        request::rotate_keys_blocking(&self.keys, |key_index| {
+            let mut request = blocking_client
+                .get(self.base_url.clone())
+                .query(&[("api_key", self.keys.get(key_index).expose())])
+                .query(params);
+            if let Some(timeout) = self.timeout {
+                request = request.timeout(timeout);
+            }
+            let reply = request::fetch_blocking(ENDPOINT, request)?;
+            self.handle_response(key_index, params, &reply)
+        })
+    }
+}
 
-        let responses = apod_resp.parse().map_err(crate::ApiError::ApodError)?;
+impl Api for ApodApi {
+    type Params = ApodParams;
+    type Response = Vec<ApodResponse>;
 
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        let (responses, _) = self.get_with_headers(params).await?;
         Ok(responses)
     }
 }
@@ -122,6 +1332,22 @@ impl From<ApodApiError> for StellariaError {
     }
 }
 
+/// Flattens `err` to its root cause, keeping anything without a matching
+/// [`ApodError`] variant as [`ApodError::Other`].
+impl From<StellariaError> for ApodError {
+    fn from(err: StellariaError) -> Self {
+        match err.into_root() {
+            StellariaError::RequestError(err) => ApodError::Network(err),
+            StellariaError::JsonError(err) => ApodError::Json(err),
+            StellariaError::TruncatedResponse { bytes_read } => {
+                ApodError::TruncatedResponse { bytes_read }
+            }
+            StellariaError::ApiError(ApiError::ApodError(err)) => err,
+            other => ApodError::Other(Box::new(other)),
+        }
+    }
+}
+
 impl From<ApodError> for StellariaError {
     fn from(err: ApodError) -> Self {
         ApiError::ApodError(err).into()
@@ -3,8 +3,15 @@
 use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::StellariaResult;
-use crate::apod::{ApodError::ApodParamsError, date_serde};
+use crate::apod::{ApodError, ApodError::ApodParamsError};
+use crate::clock::check_date_window;
+use crate::{StellariaResult, date_serde};
+
+/// The first day NASA published an Astronomy Picture of the Day.
+pub const APOD_EPOCH: NaiveDate = NaiveDate::from_ymd_opt(1995, 6, 16).unwrap();
+
+/// The largest `count` NASA accepts; above it the API answers with a 400.
+pub const MAX_COUNT: u8 = 100;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ApodParams {
@@ -23,6 +30,8 @@ pub struct ApodParams {
 pub struct ApodParamsBuilder {
     thumbs: bool,
     range: Option<ApodRange>,
+    error: Option<ApodError>,
+    today: Option<NaiveDate>,
 }
 
 #[derive(Debug)]
@@ -41,25 +50,75 @@ impl ApodParamsBuilder {
         self
     }
 
-    pub fn count(mut self, count: u8) -> Self {
-        self.range = Some(ApodRange::Count(count));
-        self
+    pub fn count(self, count: u8) -> Self {
+        self.range(ApodRange::Count(count))
     }
 
-    pub fn date(mut self, date: NaiveDate) -> Self {
-        self.range = Some(ApodRange::Date(date));
-        self
+    pub fn date(self, date: NaiveDate) -> Self {
+        self.range(ApodRange::Date(date))
+    }
+
+    /// Sets a single date from its components; an impossible date such as
+    /// Feb 30 makes [`build`](Self::build) fail.
+    pub fn date_ymd(self, year: i32, month: u32, day: u32) -> Self {
+        match NaiveDate::from_ymd_opt(year, month, day) {
+            Some(date) => self.date(date),
+            None => self.fail(format!(
+                "invalid calendar date: {year:04}-{month:02}-{day:02}"
+            )),
+        }
+    }
+
+    /// Sets a single date from a `YYYY-MM-DD` string; a malformed one makes
+    /// [`build`](Self::build) fail.
+    pub fn date_str(self, date: &str) -> Self {
+        match parse_date_str(date) {
+            Ok(date) => self.date(date),
+            Err(msg) => self.fail(msg),
+        }
     }
 
-    pub fn date_range(mut self, start_date: NaiveDate, end_date: NaiveDate) -> Self {
-        self.range = Some(ApodRange::DateRange {
+    /// Like [`date_range`](Self::date_range), from `YYYY-MM-DD` strings.
+    pub fn date_range_str(self, start_date: &str, end_date: &str) -> Self {
+        match (parse_date_str(start_date), parse_date_str(end_date)) {
+            (Ok(start_date), Ok(end_date)) => self.date_range(start_date, end_date),
+            (Err(msg), _) | (_, Err(msg)) => self.fail(msg),
+        }
+    }
+
+    pub fn date_range(self, start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        self.range(ApodRange::DateRange {
             start_date,
             end_date,
-        });
+        })
+    }
+
+    pub(crate) fn today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    /// Only one of date, count and date_range may be set; a second one makes
+    /// [`build`](Self::build) fail rather than silently replacing the first.
+    fn range(mut self, range: ApodRange) -> Self {
+        if self.range.is_some() {
+            return self.fail("date, count, and date_range are mutually exclusive".to_string());
+        }
+        self.range = Some(range);
+        self
+    }
+
+    fn fail(mut self, msg: String) -> Self {
+        self.error.get_or_insert(ApodParamsError(msg));
         self
     }
 
     pub fn build(self) -> StellariaResult<ApodParams> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+        let today = self.today.unwrap_or_else(|| Utc::now().date_naive());
+
         let mut params = ApodParams {
             thumbs: self.thumbs,
             ..Default::default()
@@ -67,14 +126,22 @@ impl ApodParamsBuilder {
 
         if let Some(range) = self.range {
             match range {
-                ApodRange::Count(count) => params.count = Some(count),
+                ApodRange::Count(count) => {
+                    if !(1..=MAX_COUNT).contains(&count) {
+                        return Err(ApodParamsError(format!(
+                            "Count must be between 1 and {MAX_COUNT}, got {count}"
+                        ))
+                        .into());
+                    }
+                    params.count = Some(count)
+                }
                 ApodRange::Date(date) => {
-                    if (date > Utc::now().date_naive())
-                        || (date < NaiveDate::from_ymd_opt(1995, 6, 16).unwrap())
-                    {
-                        return Err(ApodParamsError(
-                            "Date must be between Jun 16, 1995 and Dec 12, 2025.".to_string(),
-                        )
+                    if check_date_window(date, APOD_EPOCH, today).is_err() {
+                        return Err(ApodParamsError(format!(
+                            "Date must be between {} and {}.",
+                            APOD_EPOCH.format("%b %-d, %Y"),
+                            today.format("%b %-d, %Y"),
+                        ))
                         .into());
                     }
                     params.date = Some(date)
@@ -94,15 +161,78 @@ impl ApodParamsBuilder {
                 }
             }
         } else {
-            params.date = Some(Utc::now().date_naive());
+            params.date = Some(today);
         }
 
         Ok(params)
     }
 }
 
+fn parse_date_str(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, date_serde::FORMAT)
+        .map_err(|err| format!("invalid date {date:?}, expected YYYY-MM-DD: {err}"))
+}
+
 impl ApodParams {
     pub fn builder() -> ApodParamsBuilder {
         ApodParamsBuilder::default()
     }
+
+    /// The query pairs these params add to a request, for logging. The
+    /// api_key is added separately when sending and never appears here.
+    pub fn redacted_query(&self) -> Vec<(String, String)> {
+        let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(self) else {
+            return Vec::new();
+        };
+        fields
+            .into_iter()
+            .map(|(name, value)| match value {
+                serde_json::Value::String(value) => (name, value),
+                value => (name, value.to_string()),
+            })
+            .collect()
+    }
+
+    /// A human-readable summary for logs and UIs, e.g. `"single date 2024-12-12"`
+    /// or `"10 random pictures with thumbnails"`.
+    pub fn describe(&self) -> String {
+        let mut description = match (self.date, self.count, self.start_date, self.end_date) {
+            (Some(date), ..) => format!("single date {date}"),
+            (_, Some(1), ..) => "1 random picture".to_string(),
+            (_, Some(count), ..) => format!("{count} random pictures"),
+            (_, _, Some(start_date), Some(end_date)) => {
+                format!("range {start_date} to {end_date}")
+            }
+            (_, _, Some(start_date), None) => format!("range {start_date} to today"),
+            _ => "today's picture".to_string(),
+        };
+        if self.thumbs {
+            description.push_str(" with thumbnails");
+        }
+        description
+    }
+
+    /// Checks params that didn't come from the builder (e.g. deserialized
+    /// from JSON) for combinations NASA would reject.
+    pub fn validate(&self) -> StellariaResult<()> {
+        let has_range = self.start_date.is_some() || self.end_date.is_some();
+        let selectors = [self.date.is_some(), self.count.is_some(), has_range];
+        if selectors.into_iter().filter(|set| *set).count() > 1 {
+            return Err(ApodParamsError(
+                "date, count, and date_range are mutually exclusive".to_string(),
+            )
+            .into());
+        }
+
+        match (self.start_date, self.end_date) {
+            (None, Some(_)) => {
+                Err(ApodParamsError("end_date requires a start_date".to_string()).into())
+            }
+            (Some(start_date), Some(end_date)) if start_date > end_date => Err(ApodParamsError(
+                "Start date cannot be greater than end date".to_string(),
+            )
+            .into()),
+            _ => Ok(()),
+        }
+    }
 }
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeDelta, Utc, Weekday};
+
+/// NASA publishes each new APOD shortly after midnight US Eastern time.
+const PUBLISH_TIME: NaiveTime = NaiveTime::from_hms_opt(0, 5, 0).unwrap();
+
+/// Returns the next APOD publish boundary (00:05 US Eastern) after `now`.
+pub fn next_publish_time(now: DateTime<Utc>) -> DateTime<Utc> {
+    let eastern_today = (now - eastern_offset_at(now)).date_naive();
+
+    eastern_today
+        .iter_days()
+        .map(publish_time_on)
+        .find(|publish| *publish > now)
+        .expect("a publish time follows every instant")
+}
+
+fn publish_time_on(date: NaiveDate) -> DateTime<Utc> {
+    // Clocks change at 02:00 local, so 00:05 always has the previous day's offset.
+    let offset = if is_dst_date(date) { 4 } else { 5 };
+    date.and_time(PUBLISH_TIME).and_utc() + TimeDelta::hours(offset)
+}
+
+/// How far US Eastern time is behind UTC at `instant`.
+fn eastern_offset_at(instant: DateTime<Utc>) -> TimeDelta {
+    let year = instant.year();
+    let dst_start = dst_start(year).and_hms_opt(7, 0, 0).unwrap().and_utc();
+    let dst_end = dst_end(year).and_hms_opt(6, 0, 0).unwrap().and_utc();
+    if (dst_start..dst_end).contains(&instant) {
+        TimeDelta::hours(4)
+    } else {
+        TimeDelta::hours(5)
+    }
+}
+
+fn is_dst_date(date: NaiveDate) -> bool {
+    date > dst_start(date.year()) && date <= dst_end(date.year())
+}
+
+/// Second Sunday of March.
+fn dst_start(year: i32) -> NaiveDate {
+    nth_sunday(year, 3, 2)
+}
+
+/// First Sunday of November.
+fn dst_end(year: i32) -> NaiveDate {
+    nth_sunday(year, 11, 1)
+}
+
+fn nth_sunday(year: i32, month: u32, n: u8) -> NaiveDate {
+    let first = NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, 1)
+        .expect("every month has a first Sunday");
+    first + Days::new(7 * u64::from(n - 1))
+}
@@ -2,11 +2,21 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::apod::{ApodApi, ApodApiError, params::ApodParams};
+    use crate::apod::{
+        APOD_EPOCH, ApiResponse, ApodApi, ApodApiError, ApodError, ApodResponse, ImagePref,
+        MAX_CHUNK_DAYS, MediaType, RetryPolicy, RetryStats, merge_sorted_unique,
+        params::ApodParams, parse_date, surprise_date, to_jsonl,
+    };
+    use crate::test_util::{TEST_KEY, mock_base_url};
     use crate::{Api, StellariaError};
 
-    use chrono::NaiveDate;
+    use chrono::{Datelike, NaiveDate};
+    use futures::StreamExt;
     use reqwest::Client;
+    use serde_json::json;
+    use url::Url;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     struct Setup {
         apod: ApodApi,
@@ -25,30 +35,1538 @@ mod tests {
         }
     }
 
+    fn mock_apod(server: &MockServer) -> ApodApi {
+        ApodApi::with_base_url(
+            TEST_KEY,
+            Client::new(),
+            mock_base_url(server, "/planetary/apod"),
+        )
+        .unwrap()
+    }
+
+    fn apod_json(date: NaiveDate) -> serde_json::Value {
+        json!({
+            "date": date.to_string(),
+            "explanation": "A test picture.",
+            "media_type": "image",
+            "service_version": "v1",
+            "title": format!("Picture for {date}"),
+            "url": format!("https://apod.nasa.gov/apod/image/{date}.jpg"),
+        })
+    }
+
     // ==================== API Tests ====================
 
     #[tokio::test]
-    async fn test_apod_default() {
-        let setup = Setup::new();
-        let params = ApodParams::builder();
-        let resp = setup.apod.get(params.build().unwrap()).await;
-        assert!(resp.is_ok());
+    async fn test_apod_default() {
+        let setup = Setup::new();
+        let params = ApodParams::builder();
+        let resp = setup.apod.get(params.build().unwrap()).await;
+        assert!(resp.is_ok());
+    }
+
+    // ==================== Retry Tests ====================
+
+    fn quick_retries(server: &MockServer) -> ApodApi {
+        mock_apod(server).with_retry_policy(RetryPolicy {
+            max_retries: 3,
+            base_delay: std::time::Duration::from_millis(1),
+            max_delay: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_after_transient_failures() {
+        let server = MockServer::start().await;
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(day)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().date(day).build().unwrap();
+        let responses = quick_retries(&server).get(params).await.unwrap();
+
+        assert_eq!(responses[0].date, day);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stats_report_one_retry() {
+        let server = MockServer::start().await;
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(day)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().date(day).build().unwrap();
+        let (responses, stats) = quick_retries(&server).get_with_stats(params).await.unwrap();
+
+        assert_eq!(responses[0].date, day);
+        assert_eq!(
+            stats,
+            RetryStats {
+                attempts: 2,
+                retries: 1,
+                last_status: Some(503),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_retries() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = quick_retries(&server).get(params).await.unwrap_err();
+
+        assert_eq!(err.status(), Some(502));
+    }
+
+    #[tokio::test]
+    async fn test_retry_skips_client_errors() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = quick_retries(&server).get(params).await.unwrap_err();
+
+        assert_eq!(err.status(), Some(403));
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_retry_after() {
+        let server = MockServer::start().await;
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(day)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // Without the Retry-After the first backoff would take an hour.
+        let apod = mock_apod(&server).with_retry_policy(RetryPolicy {
+            max_retries: 1,
+            base_delay: std::time::Duration::from_secs(3600),
+            max_delay: None,
+        });
+        let params = ApodParams::builder().date(day).build().unwrap();
+        let responses = tokio::time::timeout(std::time::Duration::from_secs(5), apod.get(params))
+            .await
+            .expect("retry waited for the backoff instead of Retry-After")
+            .unwrap();
+
+        assert_eq!(responses[0].date, day);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_error_carries_retry_after() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "120"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = mock_apod(&server).get(params).await.unwrap_err();
+
+        assert!(err.is_rate_limited());
+        assert_eq!(err.retry_after(), Some(std::time::Duration::from_secs(120)));
+    }
+
+    #[tokio::test]
+    async fn test_operation_timeout_cuts_retries_short() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(
+                ResponseTemplate::new(503).set_delay(std::time::Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server)
+            .with_retry_policy(RetryPolicy {
+                max_retries: 10,
+                base_delay: std::time::Duration::from_millis(1),
+                max_delay: None,
+            })
+            .with_operation_timeout(std::time::Duration::from_millis(500));
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = apod.get(params).await.unwrap_err();
+
+        assert!(matches!(err.root(), StellariaError::Timeout(limit) if limit.as_millis() == 500));
+        assert!(err.is_timeout());
+        let attempts = server.received_requests().await.unwrap().len();
+        assert!((2..=3).contains(&attempts), "{attempts} attempts");
+    }
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: std::time::Duration::from_millis(100),
+            max_delay: None,
+        };
+
+        assert_eq!(policy.delay(0).as_millis(), 100);
+        assert_eq!(policy.delay(2).as_millis(), 400);
+
+        let capped = RetryPolicy {
+            max_delay: Some(std::time::Duration::from_millis(250)),
+            ..policy
+        };
+        assert_eq!(capped.delay(1).as_millis(), 200);
+        assert_eq!(capped.delay(2).as_millis(), 250);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_when_retry_after_exceeds_max_delay() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "3600"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server).with_retry_policy(RetryPolicy {
+            max_retries: 3,
+            base_delay: std::time::Duration::from_millis(1),
+            max_delay: Some(std::time::Duration::from_secs(60)),
+        });
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = tokio::time::timeout(std::time::Duration::from_secs(5), apod.get(params))
+            .await
+            .expect("retry waited for the Retry-After beyond max_delay")
+            .unwrap_err();
+
+        assert!(err.is_rate_limited());
+        assert_eq!(
+            err.retry_after(),
+            Some(std::time::Duration::from_secs(3600))
+        );
+    }
+
+    // ==================== Timeout Tests ====================
+
+    #[tokio::test]
+    async fn test_slow_response_times_out() {
+        let server = MockServer::start().await;
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(apod_json(day))
+                    .set_delay(std::time::Duration::from_secs(2)),
+            )
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().date(day).build().unwrap();
+        let err = mock_apod(&server)
+            .with_timeout(std::time::Duration::from_millis(50))
+            .get(params)
+            .await
+            .unwrap_err();
+
+        assert!(err.is_timeout());
+        assert!(err.is_retryable());
+        assert!(!err.to_string().contains(TEST_KEY), "{err}");
+        assert!(!format!("{err:?}").contains(TEST_KEY), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_get_apod_flattens_api_error() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "error": {"code": "API_KEY_INVALID", "message": "An invalid api_key was supplied."},
+            })))
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = mock_apod(&server).get_apod(params).await.unwrap_err();
+
+        assert!(matches!(err, ApodError::ApodApiError(_)));
+        assert_eq!(err.status(), Some(403));
+    }
+
+    #[tokio::test]
+    async fn test_get_apod_flattens_request_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let base_url = Url::parse(&format!("http://{addr}/planetary/apod")).unwrap();
+        let apod = ApodApi::with_base_url("TEST_KEY", Client::new(), base_url).unwrap();
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = apod.get_apod(params).await.unwrap_err();
+
+        assert!(matches!(err, ApodError::Network(_)));
+    }
+
+    #[test]
+    fn test_non_apod_error_flattens_to_other() {
+        let err =
+            StellariaError::ConfigError("bad config".to_string()).with_context("APOD", "today");
+        let err = ApodError::from(err);
+
+        assert!(matches!(
+            &err,
+            ApodError::Other(other) if matches!(**other, StellariaError::ConfigError(_))
+        ));
+        assert_eq!(err.to_string(), "invalid configuration: bad config");
+        assert_eq!(err.status(), None);
+    }
+
+    #[tokio::test]
+    async fn test_last_rate_limit_captured() {
+        let server = MockServer::start().await;
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("X-RateLimit-Limit", "1000")
+                    .insert_header("X-RateLimit-Remaining", "998")
+                    .set_body_json(apod_json(day)),
+            )
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server);
+        assert_eq!(apod.last_rate_limit(), None);
+
+        let params = ApodParams::builder().date(day).build().unwrap();
+        apod.get(params).await.unwrap();
+
+        assert_eq!(
+            apod.last_rate_limit(),
+            Some(crate::RateLimit {
+                limit: Some(1000),
+                remaining: Some(998),
+            })
+        );
+    }
+
+    #[test]
+    fn test_with_base_url_rejects_cannot_be_a_base() {
+        let base_url = Url::parse("mailto:apod@example.com").unwrap();
+        let result = ApodApi::with_base_url("TEST_KEY", Client::new(), base_url);
+
+        assert!(matches!(result, Err(StellariaError::UrlError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_one_day_range_single_object_normalizes_to_vec() {
+        let server = MockServer::start().await;
+        let day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("start_date", day.to_string()))
+            .and(query_param("end_date", day.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(day)))
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().date_range(day, day).build().unwrap();
+        let responses = mock_apod(&server).get(params).await.unwrap();
+
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].date, day);
+    }
+
+    #[tokio::test]
+    async fn test_stream_range_yields_in_order_across_chunks() {
+        let server = MockServer::start().await;
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let second_chunk = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 4, 11).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("start_date", "2024-01-01"))
+            .and(query_param("end_date", "2024-04-09"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                apod_json(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()),
+                apod_json(start),
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("start_date", "2024-04-10"))
+            .and(query_param("end_date", "2024-04-11"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!([apod_json(second_chunk), apod_json(end)])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server);
+        let dates: Vec<_> = apod
+            .stream_range(start, end)
+            .map(|response| response.unwrap().date)
+            .collect()
+            .await;
+
+        assert_eq!(
+            dates,
+            [
+                start,
+                NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+                second_chunk,
+                end
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_range_stops_after_failed_chunk() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server);
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let items: Vec<_> = apod.stream_range(start, end).collect().await;
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_ref().unwrap_err().status(), Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_latest_date_lets_nasa_pick_the_day() {
+        let server = MockServer::start().await;
+        let latest = NaiveDate::from_ymd_opt(2024, 12, 11).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(|request: &wiremock::Request| {
+                !request.url.query_pairs().any(|(name, _)| name == "date")
+            })
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(latest)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert_eq!(mock_apod(&server).latest_date().await.unwrap(), latest);
+    }
+
+    #[tokio::test]
+    async fn test_get_one_returns_the_single_response() {
+        let server = MockServer::start().await;
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("date", date.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(date)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().date(date).build().unwrap();
+        let response = mock_apod(&server).get_one(params).await.unwrap();
+        assert_eq!(response.date, date);
+    }
+
+    #[tokio::test]
+    async fn test_get_one_rejects_multiple_items() {
+        let server = MockServer::start().await;
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let next = NaiveDate::from_ymd_opt(2024, 3, 2).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!([apod_json(date), apod_json(next)])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server);
+        let range = ApodParams::builder()
+            .date_range(date, next)
+            .build()
+            .unwrap();
+        assert!(apod.get_one(range).await.unwrap_err().is_client_error());
+        let count = ApodParams::builder().count(2).build().unwrap();
+        assert!(apod.get_one(count).await.unwrap_err().is_client_error());
+
+        let params = ApodParams::builder().date(date).build().unwrap();
+        let err = apod.get_one(params).await.unwrap_err();
+        assert!(matches!(
+            err,
+            StellariaError::ApiError(crate::ApiError::ApodError(ApodError::NotSingle {
+                count: 2
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_surprise_date_is_deterministic() {
+        let today = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+
+        let date = surprise_date(42, today);
+        assert_eq!(surprise_date(42, today), date);
+        assert!((APOD_EPOCH..=today).contains(&date));
+        assert_ne!(surprise_date(43, today), date);
+    }
+
+    #[tokio::test]
+    async fn test_surprise_fetches_seeded_date() {
+        let server = MockServer::start().await;
+        let today = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+        let date = surprise_date(7, today);
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("date", date.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(date)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server).with_clock(crate::Clock::from_fn(move || {
+            today.and_hms_opt(12, 0, 0).unwrap().and_utc()
+        }));
+        assert_eq!(apod.surprise(Some(7)).await.unwrap().date, date);
+    }
+
+    #[tokio::test]
+    async fn test_media_type_on() {
+        let server = MockServer::start().await;
+        let image_day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let video_day = NaiveDate::from_ymd_opt(2024, 3, 2).unwrap();
+        let mut video = apod_json(video_day);
+        video["media_type"] = json!("video");
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("date", image_day.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(image_day)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("date", video_day.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(video))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let apod = mock_apod(&server);
+        assert_eq!(
+            apod.media_type_on(image_day).await.unwrap(),
+            MediaType::Image
+        );
+        assert_eq!(
+            apod.media_type_on(video_day).await.unwrap(),
+            MediaType::Video
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_available_falls_back_to_yesterday() {
+        let server = MockServer::start().await;
+        let today = chrono::Utc::now().date_naive();
+        let yesterday = today.pred_opt().unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("date", today.to_string()))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "code": 404,
+                "msg": format!("No data available for date: {today}"),
+                "service_version": "v1",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("date", yesterday.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(yesterday)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let resp = mock_apod(&server).latest_available().await.unwrap();
+        assert_eq!(resp.date, yesterday);
+    }
+
+    #[tokio::test]
+    async fn test_get_with_headers_returns_scrubbed_headers() {
+        let server = MockServer::start().await;
+        let date = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(apod_json(date))
+                    .insert_header("x-custom", "hello")
+                    .insert_header("x-echo", "/planetary/apod?api_key=TEST_KEY"),
+            )
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().date(date).build().unwrap();
+        let (resp, headers) = mock_apod(&server).get_with_headers(params).await.unwrap();
+
+        assert_eq!(resp[0].date, date);
+        assert_eq!(headers["x-custom"], "hello");
+        assert_eq!(headers["x-echo"], "/planetary/apod?api_key=***");
+    }
+
+    #[tokio::test]
+    async fn test_find_missing_dates_reports_gap() {
+        let server = MockServer::start().await;
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let body: Vec<_> = [1, 2, 4, 5]
+            .into_iter()
+            .map(|d| apod_json(day(d)))
+            .collect();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("start_date", "2024-01-01"))
+            .and(query_param("end_date", "2024-01-05"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let missing = mock_apod(&server)
+            .find_missing_dates(day(1), day(5))
+            .await
+            .unwrap();
+        assert_eq!(missing, vec![day(3)]);
+    }
+
+    #[tokio::test]
+    async fn test_chunk_days_sets_request_count() {
+        let server = MockServer::start().await;
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        mock_apod(&server)
+            .with_chunk_days(3)
+            .unwrap()
+            .find_missing_dates(day(1), day(10))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let last = requests
+            .last()
+            .unwrap()
+            .url
+            .query_pairs()
+            .collect::<Vec<_>>();
+        assert!(last.contains(&("start_date".into(), "2024-01-10".into())));
+        assert!(last.contains(&("end_date".into(), "2024-01-10".into())));
+    }
+
+    #[test]
+    fn test_chunk_days_rejects_out_of_range() {
+        let apod = || ApodApi::new("TEST_KEY", Client::new());
+
+        assert!(apod().with_chunk_days(0).is_err());
+        assert!(apod().with_chunk_days(MAX_CHUNK_DAYS + 1).is_err());
+        assert!(apod().with_chunk_days(MAX_CHUNK_DAYS).is_ok());
+    }
+
+    /// Answers one request on a fresh port with `response` verbatim, then
+    /// closes the connection.
+    async fn serve_raw(response: String) -> std::net::SocketAddr {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_truncated_body_is_detected() {
+        let partial = r#"[{"date": "2024-12-12", "title": "Cut of"#;
+        let addr = serve_raw(format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\n\r\n{partial}"
+        ))
+        .await;
+
+        let base_url = Url::parse(&format!("http://{addr}/planetary/apod")).unwrap();
+        let apod = ApodApi::with_base_url("TEST_KEY", Client::new(), base_url).unwrap();
+        let params = ApodParams::builder().count(2).build().unwrap();
+
+        let err = apod.get(params).await.unwrap_err();
+        assert!(matches!(
+            err.root(),
+            StellariaError::TruncatedResponse { bytes_read } if *bytes_read == partial.len()
+        ));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_body_shorter_than_content_length_is_truncated() {
+        let partial = r#"[{"date": "2024-12-12", "title": "Cut of"#;
+        let addr = serve_raw(format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 4096\r\n\r\n{partial}"
+        ))
+        .await;
+
+        let base_url = Url::parse(&format!("http://{addr}/planetary/apod")).unwrap();
+        let apod = ApodApi::with_base_url("TEST_KEY", Client::new(), base_url).unwrap();
+        let params = ApodParams::builder().count(2).build().unwrap();
+
+        let err = apod.get(params).await.unwrap_err();
+        assert!(matches!(
+            err.root(),
+            StellariaError::TruncatedResponse { bytes_read } if *bytes_read == partial.len()
+        ));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_structured_error_body_is_kept() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "code": 400,
+                "msg": "Date must be between Jun 16, 1995 and Dec 12, 2024.",
+                "service_version": "v1",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("count", "2"))
+            .respond_with(ResponseTemplate::new(502).set_body_string("Bad Gateway"))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().build().unwrap();
+        let Err(StellariaError::ApiError(crate::ApiError::ApodError(ApodError::ApodApiError(err)))) =
+            mock_apod(&server)
+                .get(params)
+                .await
+                .map_err(StellariaError::into_root)
+        else {
+            panic!("expected an APOD api error");
+        };
+        assert_eq!(err.code(), 400);
+        assert_eq!(
+            err.msg(),
+            "Date must be between Jun 16, 1995 and Dec 12, 2024."
+        );
+        assert_eq!(err.service_version(), "v1");
+
+        let params = ApodParams::builder().count(2).build().unwrap();
+        let Err(StellariaError::ApiError(crate::ApiError::ApodError(ApodError::ApodApiError(err)))) =
+            mock_apod(&server)
+                .get(params)
+                .await
+                .map_err(StellariaError::into_root)
+        else {
+            panic!("expected an APOD api error");
+        };
+        assert_eq!(err.code(), 502);
+        assert_eq!(err.msg(), "Bad Gateway");
+        assert_eq!(err.service_version(), "unknown");
+    }
+
+    #[tokio::test]
+    async fn test_error_names_endpoint_and_date() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("upstream down"))
+            .mount(&server)
+            .await;
+
+        let date = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+        let params = ApodParams::builder().date(date).build().unwrap();
+        let err = mock_apod(&server).get(params).await.unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.starts_with("while fetching APOD for single date 2024-12-12: "));
+        assert!(message.contains("upstream down"));
+        assert_eq!(err.status(), Some(500));
+        assert!(err.is_retryable());
+        assert!(matches!(err.root(), StellariaError::ApiError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_schema_drift_is_not_truncation() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "unexpected": true })))
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().count(2).build().unwrap();
+        let err = mock_apod(&server).get(params).await.unwrap_err();
+        assert!(matches!(err.root(), StellariaError::JsonError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_anniversaries_skips_missing_years() {
+        let server = MockServer::start().await;
+        let this_year = chrono::Utc::now().date_naive().year();
+        let first = NaiveDate::from_ymd_opt(this_year - 2, 1, 1).unwrap();
+        let third = NaiveDate::from_ymd_opt(this_year, 1, 1).unwrap();
+
+        for date in [first, third] {
+            Mock::given(method("GET"))
+                .and(path("/planetary/apod"))
+                .and(query_param("date", date.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(apod_json(date)))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "code": 404,
+                "msg": "No data available",
+                "service_version": "v1",
+            })))
+            .mount(&server)
+            .await;
+
+        let anniversaries = mock_apod(&server)
+            .anniversaries(1, 1, this_year - 2)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            anniversaries.keys().copied().collect::<Vec<_>>(),
+            vec![this_year - 2, this_year]
+        );
+        assert_eq!(anniversaries[&this_year].date, third);
+    }
+
+    #[tokio::test]
+    async fn test_anniversaries_rejects_invalid_day() {
+        let server = MockServer::start().await;
+        let result = mock_apod(&server).anniversaries(2, 30, 2000).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_summaries_drops_explanations() {
+        let server = MockServer::start().await;
+        let image = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let video = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let mut video_json = apod_json(video);
+        video_json["media_type"] = "video".into();
+        video_json["url"] = "https://www.youtube.com/embed/abc".into();
+        video_json["thumbnail_url"] = "https://img.youtube.com/vi/abc/0.jpg".into();
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!([apod_json(image), video_json])),
+            )
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder()
+            .date_range(image, video)
+            .thumbs(true)
+            .build()
+            .unwrap();
+        let summaries = mock_apod(&server).get_summaries(params).await.unwrap();
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].title, format!("Picture for {image}"));
+        assert_eq!(summaries[0].thumbnail_url, None);
+        assert_eq!(summaries[1].media_type, MediaType::Video);
+        assert_eq!(
+            summaries[1].thumbnail_url.as_ref().map(Url::as_str),
+            Some("https://img.youtube.com/vi/abc/0.jpg")
+        );
+    }
+
+    #[test]
+    fn test_fetch_all_dates_spans_epoch_to_today() {
+        let apod = ApodApi::new("TEST_KEY", Client::new());
+        let today = chrono::Utc::now().date_naive();
+
+        let dates = apod.fetch_all_dates().unwrap();
+
+        assert_eq!(dates.first(), Some(&crate::apod::APOD_EPOCH));
+        assert_eq!(dates.last(), Some(&today));
+        assert!(
+            dates
+                .windows(2)
+                .all(|pair| pair[0].succ_opt() == Some(pair[1]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_date_window_check_rejects_out_of_range_entry() {
+        let server = MockServer::start().await;
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let body = json!([apod_json(day(1)), apod_json(day(2)), apod_json(day(9))]);
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let params = || {
+            ApodParams::builder()
+                .date_range(day(1), day(3))
+                .build()
+                .unwrap()
+        };
+        let unchecked = mock_apod(&server).get(params()).await.unwrap();
+        assert_eq!(unchecked.len(), 3);
+
+        let err = mock_apod(&server)
+            .with_date_window_check(true)
+            .get(params())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.root(),
+            StellariaError::ApiError(crate::ApiError::ApodError(
+                crate::apod::ApodError::DateOutOfWindow { date, .. }
+            )) if *date == day(9)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_response_validation_rejects_empty_title() {
+        let server = MockServer::start().await;
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let mut body = apod_json(date);
+        body["title"] = json!("  ");
+
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let params = || ApodParams::builder().date(date).build().unwrap();
+        let unchecked = mock_apod(&server).get(params()).await.unwrap();
+        assert_eq!(unchecked[0].title, "  ");
+
+        let err = mock_apod(&server)
+            .with_response_validation(true)
+            .get(params())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.root(),
+            StellariaError::ApiError(crate::ApiError::ApodError(
+                ApodError::IncompleteResponse { missing }
+            )) if missing == &["title"]
+        ));
+        assert!(
+            err.to_string()
+                .ends_with("APOD response has no usable title")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_key_rotates_to_fallback() {
+        let server = MockServer::start().await;
+        let date = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+
+        Mock::given(method("GET"))
+            .and(query_param("api_key", "KEY_A"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .set_body_string("OVER_RATE_LIMIT")
+                    .insert_header("X-RateLimit-Remaining", "0"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("api_key", "KEY_C"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(apod_json(date))
+                    .insert_header("X-RateLimit-Remaining", "5"),
+            )
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("api_key", "KEY_B"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(apod_json(date))
+                    .insert_header("X-RateLimit-Remaining", "900"),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let base_url = mock_base_url(&server, "/planetary/apod");
+        let apod = ApodApi::with_base_url("KEY_A", Client::new(), base_url)
+            .unwrap()
+            .with_fallback_keys(["KEY_B", "KEY_C"]);
+        let params = || ApodParams::builder().date(date).build().unwrap();
+
+        let first = apod.get(params()).await.unwrap();
+        let second = apod.get(params()).await.unwrap();
+
+        assert_eq!(first[0].date, date);
+        assert_eq!(second[0].date, date);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_without_fallback_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).set_body_string("OVER_RATE_LIMIT"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = ApodParams::builder().count(1).build().unwrap();
+        let err = mock_apod(&server).get(params).await.unwrap_err();
+        assert!(err.is_rate_limited());
+    }
+
+    #[test]
+    fn test_apod_error_conversion() {
+        let error = ApodApiError {
+            code: 500,
+            msg: "Internal Server Error".to_string(),
+            service_version: "v1".to_string(),
+            retry_after: None,
+        };
+
+        let stellaria_error: StellariaError = error.into();
+        assert!(
+            stellaria_error
+                .to_string()
+                .contains("Internal Server Error")
+        );
+    }
+
+    // ==================== Image Preference Tests ====================
+
+    fn response_with_hdurl(hdurl: Option<&str>) -> ApodResponse {
+        let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, 12).unwrap());
+        json["url"] = "https://apod.nasa.gov/apod/image/std.jpg".into();
+        if let Some(hdurl) = hdurl {
+            json["hdurl"] = hdurl.into();
+        }
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_image_preference_selects_url() {
+        let hd = "https://apod.nasa.gov/apod/image/hd.jpg";
+        let with_hd = response_with_hdurl(Some(hd));
+        let without_hd = response_with_hdurl(None);
+
+        let url = |resp: &ApodResponse, pref| resp.image_url(pref).map(|url| url.to_string());
+        let std = Some("https://apod.nasa.gov/apod/image/std.jpg".to_string());
+
+        assert_eq!(url(&with_hd, ImagePref::Standard), std);
+        assert_eq!(url(&with_hd, ImagePref::Hd), Some(hd.to_string()));
+        assert_eq!(
+            url(&with_hd, ImagePref::HdWithFallback),
+            Some(hd.to_string())
+        );
+        assert_eq!(url(&without_hd, ImagePref::Standard), std);
+        assert_eq!(url(&without_hd, ImagePref::Hd), None);
+        assert_eq!(url(&without_hd, ImagePref::HdWithFallback), std);
+    }
+
+    #[test]
+    fn test_client_image_preference_applies_to_apod() {
+        let client = crate::StellariaClient::builder("TEST_KEY")
+            .image_preference(ImagePref::Standard)
+            .build()
+            .unwrap();
+        let with_hd = response_with_hdurl(Some("https://apod.nasa.gov/apod/image/hd.jpg"));
+
+        assert_eq!(client.apod.image_preference(), ImagePref::Standard);
+        assert_eq!(client.apod.image_url(&with_hd), Some(&with_hd.url));
+    }
+
+    #[test]
+    fn test_hd_host_allowlist_falls_back_to_url() {
+        let third_party = response_with_hdurl(Some("https://www.example.org/apod/hd.jpg"));
+        let nasa = response_with_hdurl(Some("https://apod.nasa.gov/apod/image/hd.jpg"));
+        let apod =
+            ApodApi::new("TEST_KEY", Client::new()).with_hd_host_allowlist(["apod.nasa.gov"]);
+
+        assert_eq!(apod.image_url(&third_party), Some(&third_party.url));
+        assert_eq!(apod.image_url(&nasa), nasa.hdurl.as_ref());
+
+        let hd_only = apod.with_image_preference(ImagePref::Hd);
+        assert_eq!(hd_only.image_url(&third_party), None);
+    }
+
+    #[tokio::test]
+    async fn test_download_applies_hd_host_allowlist() {
+        let server = MockServer::start().await;
+        mount_standard_and_hd(&server).await;
+        let mut response = image_response(&server, "image.jpg", "image").await;
+        response.hdurl = Some(mock_base_url(&server, "/image_hd.jpg"));
+
+        let untrusted = mock_apod(&server).with_hd_host_allowlist(["apod.nasa.gov"]);
+        assert_eq!(untrusted.download(&response).await.unwrap(), "standard");
+        let previews = untrusted.fetch_previews(&[response.clone()], 1).await;
+        assert_eq!(previews[0].1.as_ref().unwrap(), "standard");
+        let err = untrusted
+            .with_image_preference(ImagePref::Hd)
+            .download(&response)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            StellariaError::ApiError(crate::ApiError::ApodError(ApodError::NoImageUrl { .. }))
+        ));
+
+        let trusted = mock_apod(&server).with_hd_host_allowlist(["127.0.0.1"]);
+        assert_eq!(trusted.download(&response).await.unwrap(), "hd");
+    }
+
+    #[test]
+    fn test_client_hd_host_allowlist_applies_to_apod() {
+        let client = crate::StellariaClient::builder("TEST_KEY")
+            .hd_host_allowlist(["apod.nasa.gov"])
+            .build()
+            .unwrap();
+        let third_party = response_with_hdurl(Some("https://www.example.org/apod/hd.jpg"));
+
+        assert_eq!(client.apod.image_url(&third_party), Some(&third_party.url));
+    }
+
+    // ==================== Display Tests ====================
+
+    #[test]
+    fn test_display_renders_block() {
+        let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, 12).unwrap());
+        json["title"] = "The Cosmic Reef".into();
+        json["explanation"] = "word ".repeat(40).into();
+        let response: ApodResponse = serde_json::from_value(json).unwrap();
+
+        let rendered = response.to_string();
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert_eq!(lines[0], "2024-12-12 — The Cosmic Reef (image)");
+        assert_eq!(lines[1], "");
+        assert!(lines[2..5].iter().all(|line| line.len() <= 80));
+        assert_eq!(lines[2].len(), 79);
+        assert_eq!(lines[5], "");
+        assert_eq!(lines[6], "https://apod.nasa.gov/apod/image/2024-12-12.jpg");
+        assert_eq!(lines.len(), 7);
+    }
+
+    // ==================== Image Dimension Tests ====================
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x02\x80\x00\x00\x01\xe0\x08\x02\x00\x00\x00";
+    const JPEG_HEADER: &[u8] = &[
+        0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00, 0x01, 0x01, 0x00, 0x00,
+        0x01, 0x00, 0x01, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x03, 0x20, 0x04, 0xB0, 0x03,
+        0x01, 0x22, 0x00, 0x02, 0x11, 0x01, 0x03, 0x11, 0x01,
+    ];
+
+    async fn image_response(server: &MockServer, file: &str, media_type: &str) -> ApodResponse {
+        let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, 12).unwrap());
+        json["media_type"] = media_type.into();
+        json["url"] = format!("{}/{file}", server.uri()).into();
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_copyright_is_trimmed() {
+        let copyright = |value: serde_json::Value| {
+            let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, 12).unwrap());
+            json["copyright"] = value;
+            serde_json::from_value::<ApodResponse>(json)
+                .unwrap()
+                .copyright
+        };
+
+        assert_eq!(copyright(json!("\n  Jane  \n")), Some("Jane".to_string()));
+        assert_eq!(copyright(json!(" \n\t ")), None);
+        assert_eq!(copyright(serde_json::Value::Null), None);
+
+        let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, 12).unwrap());
+        json["copyright"] = json!("\nJohn Doe\n");
+        let response: ApodResponse = serde_json::from_value(json).unwrap();
+        let round_trip: ApodResponse =
+            serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+        assert_eq!(round_trip.copyright.as_deref(), Some("John Doe"));
+    }
+
+    #[test]
+    fn test_media_type_deserialization() {
+        let media_type = |value: &str| {
+            let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, 12).unwrap());
+            json["media_type"] = value.into();
+            serde_json::from_value::<ApodResponse>(json).unwrap()
+        };
+
+        assert!(media_type("image").is_image());
+        assert!(media_type("video").is_video());
+        let gif = media_type("gif");
+        assert_eq!(gif.media_type, MediaType::Other);
+        assert!(!gif.is_image() && !gif.is_video());
+    }
+
+    #[tokio::test]
+    async fn test_dimensions_reads_png_and_jpeg_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/image.png"))
+            .respond_with(ResponseTemplate::new(206).set_body_bytes(PNG_HEADER))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/image.jpg"))
+            .respond_with(ResponseTemplate::new(206).set_body_bytes(JPEG_HEADER))
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let png = image_response(&server, "image.png", "image").await;
+        let jpeg = image_response(&server, "image.jpg", "image").await;
+
+        assert_eq!(png.dimensions(&client).await.unwrap(), Some((640, 480)));
+        assert_eq!(jpeg.dimensions(&client).await.unwrap(), Some((1200, 800)));
     }
 
-    #[test]
-    fn test_apod_error_conversion() {
-        let error = ApodApiError {
-            code: 500,
-            msg: "Internal Server Error".to_string(),
-            service_version: "v1".to_string(),
+    #[tokio::test]
+    async fn test_dimensions_skips_videos() {
+        let server = MockServer::start().await;
+        let video = image_response(&server, "video.mp4", "video").await;
+
+        assert_eq!(video.dimensions(&Client::new()).await.unwrap(), None);
+    }
+
+    // ==================== Download Tests ====================
+
+    /// Serves `/image.jpg` as `standard` and `/image_hd.jpg` as `hd`.
+    async fn mount_standard_and_hd(server: &MockServer) {
+        for (file, body) in [("/image.jpg", "standard"), ("/image_hd.jpg", "hd")] {
+            Mock::given(method("GET"))
+                .and(path(file))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body))
+                .mount(server)
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_follows_image_preference() {
+        let server = MockServer::start().await;
+        mount_standard_and_hd(&server).await;
+
+        let client = Client::new();
+        let mut response = image_response(&server, "image.jpg", "image").await;
+        let download = |response: ApodResponse, pref| {
+            let client = client.clone();
+            async move { response.download(&client, pref).await }
         };
 
-        let stellaria_error: StellariaError = error.into();
-        assert!(
-            stellaria_error
-                .to_string()
-                .contains("Internal Server Error")
+        assert_eq!(
+            download(response.clone(), ImagePref::HdWithFallback)
+                .await
+                .unwrap(),
+            "standard"
+        );
+        let err = download(response.clone(), ImagePref::Hd).await.unwrap_err();
+        assert!(matches!(
+            err,
+            StellariaError::ApiError(crate::ApiError::ApodError(ApodError::NoImageUrl { .. }))
+        ));
+
+        response.hdurl = Some(mock_base_url(&server, "/image_hd.jpg"));
+        for (pref, expected) in [
+            (ImagePref::Standard, "standard"),
+            (ImagePref::Hd, "hd"),
+            (ImagePref::HdWithFallback, "hd"),
+        ] {
+            assert_eq!(
+                download(response.clone(), pref).await.unwrap(),
+                expected,
+                "{pref:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_download_uses_configured_preference() {
+        let server = MockServer::start().await;
+        mount_standard_and_hd(&server).await;
+
+        let mut response = image_response(&server, "image.jpg", "image").await;
+        response.hdurl = Some(mock_base_url(&server, "/image_hd.jpg"));
+        for (pref, expected) in [
+            (ImagePref::Standard, "standard"),
+            (ImagePref::Hd, "hd"),
+            (ImagePref::HdWithFallback, "hd"),
+        ] {
+            let apod = mock_apod(&server).with_image_preference(pref);
+            assert_eq!(
+                apod.download(&response).await.unwrap(),
+                expected,
+                "{pref:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_to_writes_file() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/image.jpg"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(JPEG_HEADER))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = image_response(&server, "image.jpg", "image").await;
+        let file = std::env::temp_dir().join(format!("stellaria-apod-{}.jpg", std::process::id()));
+        mock_apod(&server)
+            .download_to(&response, &file)
+            .await
+            .unwrap();
+
+        let written = std::fs::read(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(written, JPEG_HEADER);
+    }
+
+    #[tokio::test]
+    async fn test_archive_to_writes_metadata_and_image() {
+        let server = MockServer::start().await;
+        let image_day = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let video_day = NaiveDate::from_ymd_opt(2024, 3, 2).unwrap();
+        let mut image = apod_json(image_day);
+        image["url"] = json!(format!("{}/image/Nebula.PNG", server.uri()));
+        let mut video = apod_json(video_day);
+        video["media_type"] = json!("video");
+        video["url"] = json!("https://www.youtube.com/embed/abc123");
+
+        for (day, body) in [(image_day, image), (video_day, video)] {
+            Mock::given(method("GET"))
+                .and(path("/planetary/apod"))
+                .and(query_param("date", day.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/image/Nebula.PNG"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(JPEG_HEADER))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = std::env::temp_dir().join(format!("stellaria-archive-{}", std::process::id()));
+        let apod = mock_apod(&server);
+        let image_files = apod.archive_to(image_day, &dir, &Client::new()).await;
+        let video_files = apod.archive_to(video_day, &dir, &Client::new()).await;
+        let read = |name: &str| std::fs::read(dir.join(name));
+        let metadata = read("2024-03-01.json");
+        let image = read("2024-03-01.png");
+        let shortcut = read("2024-03-02.url");
+        let video_image = dir.join("2024-03-02.jpg").exists();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            image_files.unwrap(),
+            [dir.join("2024-03-01.json"), dir.join("2024-03-01.png")]
         );
+        assert_eq!(video_files.unwrap()[1], dir.join("2024-03-02.url"));
+        let metadata: ApodResponse = serde_json::from_slice(&metadata.unwrap()).unwrap();
+        assert_eq!(metadata.date, image_day);
+        assert_eq!(image.unwrap(), JPEG_HEADER);
+        assert_eq!(
+            String::from_utf8(shortcut.unwrap()).unwrap(),
+            "[InternetShortcut]\nURL=https://www.youtube.com/embed/abc123\n"
+        );
+        assert!(!video_image);
+    }
+
+    #[tokio::test]
+    async fn test_download_rejects_videos() {
+        let server = MockServer::start().await;
+        let video = image_response(&server, "video.mp4", "video").await;
+
+        let err = video
+            .download(&Client::new(), ImagePref::Standard)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            StellariaError::ApiError(crate::ApiError::ApodError(
+                ApodError::VideoNotDownloadable { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_previews_picks_image_or_thumbnail() {
+        let server = MockServer::start().await;
+        for (file, body) in [("image.jpg", "image"), ("thumb.jpg", "thumbnail")] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{file}")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/video.mp4"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let image = image_response(&server, "image.jpg", "image").await;
+        let mut video = image_response(&server, "video.mp4", "video").await;
+        video.date = NaiveDate::from_ymd_opt(2024, 12, 13).unwrap();
+        video.thumbnail_url = Some(mock_base_url(&server, "/thumb.jpg"));
+        let mut bare_video = video.clone();
+        bare_video.date = NaiveDate::from_ymd_opt(2024, 12, 14).unwrap();
+        bare_video.thumbnail_url = None;
+
+        let previews = mock_apod(&server)
+            .fetch_previews(&[image, video, bare_video], 2)
+            .await;
+
+        let dates: Vec<_> = previews.iter().map(|(date, _)| date.day()).collect();
+        assert_eq!(dates, [12, 13, 14]);
+        assert_eq!(previews[0].1.as_ref().unwrap().as_ref(), b"image");
+        assert_eq!(previews[1].1.as_ref().unwrap().as_ref(), b"thumbnail");
+        assert!(matches!(
+            previews[2].1,
+            Err(StellariaError::ApiError(crate::ApiError::ApodError(
+                ApodError::MissingThumbnail { .. }
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_previews_follows_image_preference() {
+        let server = MockServer::start().await;
+        mount_standard_and_hd(&server).await;
+
+        let mut response = image_response(&server, "image.jpg", "image").await;
+        response.hdurl = Some(mock_base_url(&server, "/image_hd.jpg"));
+        let mut without_hd = response.clone();
+        without_hd.date = NaiveDate::from_ymd_opt(2024, 12, 13).unwrap();
+        without_hd.hdurl = None;
+
+        let previews = mock_apod(&server)
+            .with_image_preference(ImagePref::Hd)
+            .fetch_previews(&[response.clone(), without_hd], 2)
+            .await;
+        assert_eq!(previews[0].1.as_ref().unwrap().as_ref(), b"hd");
+        assert!(matches!(
+            previews[1].1,
+            Err(StellariaError::ApiError(crate::ApiError::ApodError(
+                ApodError::NoImageUrl { .. }
+            )))
+        ));
+
+        let previews = mock_apod(&server)
+            .with_image_preference(ImagePref::Standard)
+            .fetch_previews(&[response], 1)
+            .await;
+        assert_eq!(previews[0].1.as_ref().unwrap().as_ref(), b"standard");
     }
 
     // ==================== Builder Pattern Tests ====================
@@ -80,6 +1598,37 @@ mod tests {
         assert_eq!(params.date, None);
     }
 
+    #[test]
+    fn test_builder_rejects_conflicting_selectors() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+
+        for builder in [
+            ApodParams::builder().date(date).count(5),
+            ApodParams::builder().count(5).date_range(date, end),
+            ApodParams::builder().date_range(date, end).date(date),
+        ] {
+            let err = builder.build().unwrap_err();
+            assert!(err.is_client_error());
+            assert!(
+                err.to_string()
+                    .contains("date, count, and date_range are mutually exclusive")
+            );
+        }
+    }
+
+    #[test]
+    fn test_builder_count_bounds() {
+        for count in [0, 101] {
+            let err = ApodParams::builder().count(count).build().unwrap_err();
+            assert!(err.is_client_error());
+            assert!(err.to_string().contains("between 1 and 100"));
+        }
+
+        let params = ApodParams::builder().count(100).build().unwrap();
+        assert_eq!(params.count, Some(100));
+    }
+
     #[test]
     fn test_builder_with_date_range() {
         let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
@@ -96,6 +1645,60 @@ mod tests {
         assert_eq!(params.count, None);
     }
 
+    #[test]
+    fn test_builder_with_date_ymd() {
+        let params = ApodParams::builder()
+            .date_ymd(2024, 12, 12)
+            .build()
+            .unwrap();
+
+        assert_eq!(params.date, NaiveDate::from_ymd_opt(2024, 12, 12));
+    }
+
+    #[test]
+    fn test_builder_with_invalid_date_ymd_fails() {
+        let result = ApodParams::builder().date_ymd(2024, 2, 30).build();
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("invalid calendar date"));
+        assert!(err.contains("2024-02-30"));
+    }
+
+    #[test]
+    fn test_builder_with_date_strings() {
+        let params = ApodParams::builder()
+            .date_str("2024-12-12")
+            .build()
+            .unwrap();
+        assert_eq!(params.date, NaiveDate::from_ymd_opt(2024, 12, 12));
+
+        let params = ApodParams::builder()
+            .date_range_str("2024-01-01", "2024-01-31")
+            .build()
+            .unwrap();
+        assert_eq!(params.start_date, NaiveDate::from_ymd_opt(2024, 1, 1));
+        assert_eq!(params.end_date, NaiveDate::from_ymd_opt(2024, 1, 31));
+    }
+
+    #[test]
+    fn test_builder_with_malformed_date_strings_fails() {
+        let err = ApodParams::builder()
+            .date_str("12/12/2024")
+            .build()
+            .unwrap_err();
+        assert!(err.is_client_error());
+        assert!(
+            err.to_string()
+                .contains(r#"invalid date "12/12/2024", expected YYYY-MM-DD"#)
+        );
+
+        let err = ApodParams::builder()
+            .date_range_str("2024-01-01", "2024-13-01")
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains(r#"invalid date "2024-13-01""#));
+    }
+
     #[test]
     fn test_builder_with_thumbs() {
         let params = ApodParams::builder().thumbs(true).build().unwrap();
@@ -123,6 +1726,20 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_too_late_message_names_today() {
+        let future = NaiveDate::from_ymd_opt(2099, 12, 31).unwrap();
+        let today = chrono::Utc::now().date_naive();
+        let err = ApodParams::builder()
+            .date(future)
+            .build()
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("Jun 16, 1995"));
+        assert!(err.contains(&today.format("%b %-d, %Y").to_string()));
+    }
+
     #[test]
     fn test_recent_valid_date_succeeds() {
         let valid = NaiveDate::from_ymd_opt(2024, 12, 1).unwrap();
@@ -159,6 +1776,172 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    // ==================== Describe Tests ====================
+
+    #[test]
+    fn test_describe_each_variant() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let describe =
+            |builder: crate::apod::ApodParamsBuilder| builder.build().unwrap().describe();
+
+        assert_eq!(
+            describe(ApodParams::builder().date(date)),
+            "single date 2024-12-12"
+        );
+        assert_eq!(
+            describe(ApodParams::builder().count(10).thumbs(true)),
+            "10 random pictures with thumbnails"
+        );
+        assert_eq!(describe(ApodParams::builder().count(1)), "1 random picture");
+        assert_eq!(
+            describe(ApodParams::builder().date_range(start, end)),
+            "range 2024-01-01 to 2024-01-31"
+        );
+        assert_eq!(ApodParams::default().describe(), "today's picture");
+
+        let open_range: ApodParams =
+            serde_json::from_str(r#"{"start_date": "2024-01-01", "thumbs": false}"#).unwrap();
+        assert_eq!(open_range.describe(), "range 2024-01-01 to today");
+    }
+
+    #[test]
+    fn test_redacted_query_has_no_api_key() {
+        let params = ApodParams::builder()
+            .date_range(
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+            )
+            .thumbs(true)
+            .build()
+            .unwrap();
+
+        let query = params.redacted_query();
+        assert!(query.iter().all(|(name, _)| name != "api_key"));
+        assert_eq!(
+            query,
+            [
+                ("end_date".to_string(), "2024-01-31".to_string()),
+                ("start_date".to_string(), "2024-01-01".to_string()),
+                ("thumbs".to_string(), "true".to_string()),
+            ]
+        );
+    }
+
+    // ==================== Publish Schedule Tests ====================
+
+    fn utc(s: &str) -> chrono::DateTime<chrono::Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_next_publish_time_in_standard_time() {
+        use crate::apod::next_publish_time;
+
+        assert_eq!(
+            next_publish_time(utc("2025-01-15T05:04:00Z")),
+            utc("2025-01-15T05:05:00Z")
+        );
+        assert_eq!(
+            next_publish_time(utc("2025-01-15T05:05:00Z")),
+            utc("2025-01-16T05:05:00Z")
+        );
+    }
+
+    #[test]
+    fn test_next_publish_time_across_spring_forward() {
+        use crate::apod::next_publish_time;
+
+        // DST starts on Sunday 2025-03-09 at 02:00 local.
+        assert_eq!(
+            next_publish_time(utc("2025-03-08T12:00:00Z")),
+            utc("2025-03-09T05:05:00Z")
+        );
+        assert_eq!(
+            next_publish_time(utc("2025-03-09T12:00:00Z")),
+            utc("2025-03-10T04:05:00Z")
+        );
+    }
+
+    #[test]
+    fn test_next_publish_time_across_fall_back() {
+        use crate::apod::next_publish_time;
+
+        // DST ends on Sunday 2025-11-02 at 02:00 local.
+        assert_eq!(
+            next_publish_time(utc("2025-11-01T12:00:00Z")),
+            utc("2025-11-02T04:05:00Z")
+        );
+        assert_eq!(
+            next_publish_time(utc("2025-11-02T12:00:00Z")),
+            utc("2025-11-03T05:05:00Z")
+        );
+        // 23:30 EST on Nov 2 is already Nov 3 in UTC.
+        assert_eq!(
+            next_publish_time(utc("2025-11-03T04:30:00Z")),
+            utc("2025-11-03T05:05:00Z")
+        );
+    }
+
+    // ==================== Day Count Tests ====================
+
+    #[test]
+    fn test_count_days() {
+        use crate::apod::count_days;
+
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+
+        assert_eq!(count_days(date(3, 1), date(3, 1)).unwrap(), 1);
+        assert_eq!(count_days(date(3, 1), date(3, 31)).unwrap(), 31);
+        assert!(count_days(date(3, 31), date(3, 1)).is_err());
+    }
+
+    #[test]
+    fn test_count_days_clamps_to_window() {
+        use crate::apod::{APOD_EPOCH, count_days_until};
+
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let future = NaiveDate::from_ymd_opt(2099, 1, 1).unwrap();
+
+        assert_eq!(
+            count_days_until(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), future, today).unwrap(),
+            10
+        );
+        assert_eq!(
+            count_days_until(
+                NaiveDate::from_ymd_opt(1990, 1, 1).unwrap(),
+                APOD_EPOCH,
+                today
+            )
+            .unwrap(),
+            1
+        );
+        assert_eq!(count_days_until(future, future, today).unwrap(), 0);
+    }
+
+    // ==================== Date Parsing Tests ====================
+
+    #[test]
+    fn test_parse_date_accepted_formats() {
+        let expected = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+
+        assert_eq!(parse_date("2024-12-12").unwrap(), expected);
+        assert_eq!(parse_date("2024/12/12").unwrap(), expected);
+        assert_eq!(parse_date("20241212").unwrap(), expected);
+        assert_eq!(parse_date(" 2024-12-12 ").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_date_rejects_invalid() {
+        let result = parse_date("12/12/2024");
+
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("invalid date"));
+        assert!(err.contains("12/12/2024"));
+    }
+
     // ==================== Serialization Tests ====================
 
     #[test]
@@ -212,6 +1995,176 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_response_date_accepts_datetime() {
+        let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, 12).unwrap());
+        json["date"] = "2024-12-12T05:00:00-05:00".into();
+        let with_offset: ApodResponse = serde_json::from_value(json.clone()).unwrap();
+
+        json["date"] = "2024-12-12T05:00:00".into();
+        let without_offset: ApodResponse = serde_json::from_value(json.clone()).unwrap();
+
+        let expected = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+        assert_eq!(with_offset.date, expected);
+        assert_eq!(without_offset.date, expected);
+
+        json["date"] = "December 12, 2024".into();
+        assert!(serde_json::from_value::<ApodResponse>(json).is_err());
+    }
+
+    #[test]
+    fn test_many_skips_entry_without_url() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let second = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let third = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+        let mut broken = apod_json(second);
+        broken["url"] = serde_json::Value::Null;
+
+        let body = json!([apod_json(first), broken, apod_json(third)]);
+        let parse = |skip_malformed| {
+            serde_json::from_value::<ApiResponse>(body.clone())
+                .unwrap()
+                .parse(skip_malformed)
+        };
+
+        let (responses, skipped) = parse(true).unwrap();
+        let dates: Vec<_> = responses.iter().map(|resp| resp.date).collect();
+        assert_eq!(dates, vec![first, third]);
+        let skipped = skipped.unwrap();
+        assert_eq!(skipped.count, 1);
+        assert!(
+            skipped.first_error.contains("representing an URL"),
+            "{}",
+            skipped.first_error
+        );
+        assert!(matches!(parse(false), Err(StellariaError::JsonError(_))));
+    }
+
+    #[test]
+    fn test_many_fails_when_every_entry_is_malformed() {
+        let mut broken = apod_json(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+        broken["url"] = serde_json::Value::Null;
+
+        let result = serde_json::from_value::<ApiResponse>(json!([broken.clone(), broken]))
+            .unwrap()
+            .parse(true);
+        assert!(matches!(result, Err(StellariaError::JsonError(_))));
+
+        let empty = serde_json::from_value::<ApiResponse>(json!([]))
+            .unwrap()
+            .parse(true);
+        let (responses, skipped) = empty.unwrap();
+        assert!(responses.is_empty());
+        assert!(skipped.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_entries_fail_unless_skipped() {
+        let server = MockServer::start().await;
+        let day = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let mut broken = apod_json(day.succ_opt().unwrap());
+        broken["url"] = serde_json::Value::Null;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([apod_json(day), broken])))
+            .mount(&server)
+            .await;
+        let params = || ApodParams::builder().count(2).build().unwrap();
+
+        let err = mock_apod(&server).get(params()).await.unwrap_err();
+        assert!(matches!(err.root(), StellariaError::JsonError(_)));
+
+        let apod = mock_apod(&server).with_malformed_entries_skipped(true);
+        assert_eq!(apod.last_skipped_entries(), None);
+        let responses = apod.get(params()).await.unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].date, day);
+        assert_eq!(
+            apod.last_skipped_entries().map(|skipped| skipped.count),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_validate_accepts_builder_output() {
+        let params = ApodParams::builder().count(3).build().unwrap();
+        assert!(params.validate().is_ok());
+
+        let json = r#"{"start_date": "2024-01-01", "thumbs": false}"#;
+        let params: ApodParams = serde_json::from_str(json).unwrap();
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_conflicting_json() {
+        let conflicting = [
+            r#"{"date": "2024-01-01", "count": 3, "thumbs": false}"#,
+            r#"{"count": 3, "start_date": "2024-01-01", "thumbs": false}"#,
+            r#"{"count": 3, "end_date": "2024-01-31", "thumbs": false}"#,
+            r#"{"count": 3, "start_date": "2024-01-01", "end_date": "2024-01-31", "thumbs": false}"#,
+            r#"{"date": "2024-01-01", "start_date": "2024-01-01", "end_date": "2024-01-31", "thumbs": false}"#,
+        ];
+
+        for json in conflicting {
+            let params: ApodParams = serde_json::from_str(json).unwrap();
+            let err = params.validate().unwrap_err().to_string();
+            assert!(err.contains("mutually exclusive"), "{json}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_range() {
+        let end_only = r#"{"end_date": "2024-01-31", "thumbs": false}"#;
+        let reversed = r#"{"start_date": "2024-02-01", "end_date": "2024-01-31", "thumbs": false}"#;
+
+        for json in [end_only, reversed] {
+            let params: ApodParams = serde_json::from_str(json).unwrap();
+            assert!(params.validate().is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_merge_sorted_unique() {
+        let response = |day: u32, title: &str| {
+            let mut json = apod_json(NaiveDate::from_ymd_opt(2024, 12, day).unwrap());
+            json["title"] = title.into();
+            serde_json::from_value::<ApodResponse>(json).unwrap()
+        };
+
+        let merged = merge_sorted_unique(vec![
+            vec![response(12, "first"), response(10, "first")],
+            vec![response(11, "second"), response(12, "second")],
+            vec![],
+        ]);
+
+        let merged: Vec<_> = merged
+            .iter()
+            .map(|r| (r.date.day(), r.title.as_str()))
+            .collect();
+        assert_eq!(merged, [(10, "first"), (11, "second"), (12, "first")]);
+    }
+
+    #[test]
+    fn test_to_jsonl_writes_one_response_per_line() {
+        let responses: Vec<ApodResponse> = (1..=3)
+            .map(|d| {
+                let json = apod_json(NaiveDate::from_ymd_opt(2024, 1, d).unwrap());
+                serde_json::from_value(json).unwrap()
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        to_jsonl(&responses, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert_eq!(output.lines().count(), 3);
+        for (line, original) in output.lines().zip(&responses) {
+            let parsed: ApodResponse = serde_json::from_str(line).unwrap();
+            assert_eq!(parsed.date, original.date);
+            assert_eq!(parsed.title, original.title);
+        }
+    }
+
     #[test]
     fn test_round_trip_serialization() {
         let date = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
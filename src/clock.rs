@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// The source of "now" for every date default in the crate, so tests can
+/// freeze time for all endpoints at once.
+#[derive(Clone)]
+pub struct Clock(Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>);
+
+impl Clock {
+    pub fn system() -> Self {
+        Self(Arc::new(Utc::now))
+    }
+
+    pub fn fixed(now: DateTime<Utc>) -> Self {
+        Self(Arc::new(move || now))
+    }
+
+    pub fn from_fn(now: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(now))
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        (self.0)()
+    }
+
+    /// Today's date in UTC.
+    pub fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+/// Which side of an endpoint's archive a date falls on.
+#[cfg_attr(not(any(feature = "apod", feature = "epic")), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutsideWindow {
+    BeforeEpoch,
+    AfterToday,
+}
+
+/// Checks that `date` lies between `epoch`, an archive's first day, and
+/// `today`, both inclusive.
+#[cfg_attr(not(any(feature = "apod", feature = "epic")), allow(dead_code))]
+pub(crate) fn check_date_window(
+    date: NaiveDate,
+    epoch: NaiveDate,
+    today: NaiveDate,
+) -> Result<(), OutsideWindow> {
+    if date < epoch {
+        Err(OutsideWindow::BeforeEpoch)
+    } else if date > today {
+        Err(OutsideWindow::AfterToday)
+    } else {
+        Ok(())
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+impl std::fmt::Debug for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Clock").field(&self.now()).finish()
+    }
+}
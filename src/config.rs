@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use crate::{StellariaClient, StellariaError, StellariaResult};
+
+/// Read by [`StellariaClient::from_env`], in order of precedence.
+const KEY_ENV_VARS: [&str; 2] = ["API_TOKEN", "NASA_API_KEY"];
+/// NASA's shared key for trying the API out; it has a low hourly quota.
+const DEMO_KEY: &str = "DEMO_KEY";
+
+impl StellariaClient {
+    /// A client using NASA's rate-limited `DEMO_KEY`, for trying things out
+    /// before signing up for a key.
+    pub fn demo() -> Self {
+        Self::new(DEMO_KEY)
+    }
+
+    /// Builds a client with the key in `API_TOKEN`, falling back to
+    /// `NASA_API_KEY`.
+    pub fn from_env() -> StellariaResult<Self> {
+        Self::from_env_with(|name| std::env::var(name).ok())
+    }
+
+    pub(crate) fn from_env_with(env: impl Fn(&str) -> Option<String>) -> StellariaResult<Self> {
+        let key = KEY_ENV_VARS
+            .into_iter()
+            .find_map(|name| env(name).filter(|key| !key.is_empty()))
+            .ok_or_else(|| {
+                StellariaError::ConfigError(format!("no api key in {}", KEY_ENV_VARS.join(" or ")))
+            })?;
+        StellariaClient::builder(key).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_env_prefers_api_token() {
+        let client = StellariaClient::from_env_with(|name| match name {
+            "API_TOKEN" => Some("TOKEN_KEY".to_string()),
+            "NASA_API_KEY" => Some("NASA_KEY".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(client.api_token.expose(), "TOKEN_KEY");
+
+        let client = StellariaClient::from_env_with(|name| {
+            (name == "NASA_API_KEY").then(|| "NASA_KEY".to_string())
+        })
+        .unwrap();
+        assert_eq!(client.api_token.expose(), "NASA_KEY");
+    }
+
+    #[test]
+    fn test_from_env_without_key_fails() {
+        let result = StellariaClient::from_env_with(|_| None);
+
+        let Err(StellariaError::ConfigError(msg)) = result else {
+            panic!("expected a config error");
+        };
+        assert!(msg.contains("API_TOKEN or NASA_API_KEY"));
+    }
+
+    #[test]
+    fn test_demo_uses_demo_key() {
+        assert_eq!(StellariaClient::demo().api_token.expose(), "DEMO_KEY");
+    }
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub(crate) const FORMAT: &str = "%Y-%m-%d";
+const ALT_FORMATS: [&str; 2] = ["%Y/%m/%d", "%Y%m%d"];
+
+pub fn serialize<S>(date: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match date {
+        Some(d) => serializer.serialize_str(&d.format(FORMAT).to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = Option::<String>::deserialize(deserializer)?;
+    s.map(|date_str| parse_lenient(&date_str).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+pub fn deserialize_date<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_lenient(&s).map_err(serde::de::Error::custom)
+}
+
+/// Parses `YYYY-MM-DD`, also accepting an ISO 8601 datetime truncated to its date.
+fn parse_lenient(s: &str) -> Result<NaiveDate, chrono::ParseError> {
+    NaiveDate::parse_from_str(s, FORMAT).or_else(|err| {
+        DateTime::parse_from_rfc3339(s)
+            .map(|datetime| datetime.date_naive())
+            .or_else(|_| {
+                NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f").map(|dt| dt.date())
+            })
+            .map_err(|_| err)
+    })
+}
+
+pub(crate) fn parse_user_date(input: &str) -> Option<NaiveDate> {
+    let input = input.trim();
+    std::iter::once(FORMAT)
+        .chain(ALT_FORMATS)
+        .find_map(|format| NaiveDate::parse_from_str(input, format).ok())
+}
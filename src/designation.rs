@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Normalizes small-body designations as they appear across NASA's APIs,
+//! e.g. `"3542519"`, `"(2010 PK9)"` or `"433 Eros (A898 PA)"`.
+
+const PACKED_DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// SPK-IDs of numbered asteroids are the asteroid number offset by this.
+const SPK_NUMBERED_OFFSET: u32 = 2_000_000;
+const SPK_UNNUMBERED_START: u32 = 3_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Designation {
+    /// A numbered asteroid, e.g. `433 Eros`.
+    Numbered { number: u32, name: Option<String> },
+    /// A provisional designation, e.g. `2010 PK9`.
+    Provisional {
+        year: u16,
+        half_month: char,
+        order: char,
+        cycle: u16,
+    },
+    /// A JPL SPK-ID of an unnumbered object, which has no MPC form.
+    Spk(u32),
+}
+
+impl Designation {
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let input = input
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .unwrap_or(input)
+            .trim();
+
+        if input.chars().all(|c| c.is_ascii_digit()) {
+            return Self::parse_id(input);
+        }
+        if let Some(provisional) = Self::parse_provisional(input) {
+            return Some(provisional);
+        }
+
+        // "433 Eros" or "433 Eros (A898 PA)"
+        let name_part = input.split('(').next().unwrap_or(input).trim();
+        let (number, name) = name_part.split_once(' ')?;
+        let number = number.parse().ok()?;
+        let name = name.trim();
+        Some(Designation::Numbered {
+            number,
+            name: (!name.is_empty()).then(|| name.to_string()),
+        })
+    }
+
+    fn parse_id(digits: &str) -> Option<Self> {
+        let id: u32 = digits.parse().ok()?;
+        Some(match id {
+            SPK_UNNUMBERED_START.. => Designation::Spk(id),
+            SPK_NUMBERED_OFFSET.. => Designation::Numbered {
+                number: id - SPK_NUMBERED_OFFSET,
+                name: None,
+            },
+            _ => Designation::Numbered {
+                number: id,
+                name: None,
+            },
+        })
+    }
+
+    fn parse_provisional(input: &str) -> Option<Self> {
+        let (year, rest) = input.split_once(' ')?;
+        if year.len() != 4 {
+            return None;
+        }
+        let year = year.parse().ok()?;
+
+        let mut chars = rest.chars();
+        let half_month = chars.next().filter(char::is_ascii_uppercase)?;
+        let order = chars.next().filter(char::is_ascii_uppercase)?;
+        let cycle = chars.as_str();
+        let cycle = if cycle.is_empty() {
+            0
+        } else {
+            cycle.parse().ok()?
+        };
+
+        Some(Designation::Provisional {
+            year,
+            half_month,
+            order,
+            cycle,
+        })
+    }
+
+    /// The human-readable key, e.g. `"433"` or `"2010 PK9"`.
+    pub fn primary(&self) -> String {
+        match self {
+            Designation::Numbered { number, .. } => number.to_string(),
+            Designation::Provisional {
+                year,
+                half_month,
+                order,
+                cycle,
+            } => match cycle {
+                0 => format!("{year} {half_month}{order}"),
+                _ => format!("{year} {half_month}{order}{cycle}"),
+            },
+            Designation::Spk(id) => id.to_string(),
+        }
+    }
+
+    /// The MPC packed form, e.g. `"00433"` or `"K10P09K"`. SPK-IDs of
+    /// unnumbered objects have no packed form.
+    pub fn packed(&self) -> Option<String> {
+        match self {
+            Designation::Numbered { number, .. } => pack_number(*number),
+            Designation::Provisional {
+                year,
+                half_month,
+                order,
+                cycle,
+            } => {
+                let century = PACKED_DIGITS.get(usize::from(year / 100))?;
+                let cycle_hi = PACKED_DIGITS.get(usize::from(cycle / 10))?;
+                Some(format!(
+                    "{}{:02}{half_month}{}{}{order}",
+                    char::from(*century),
+                    year % 100,
+                    char::from(*cycle_hi),
+                    cycle % 10,
+                ))
+            }
+            Designation::Spk(_) => None,
+        }
+    }
+}
+
+fn pack_number(number: u32) -> Option<String> {
+    match number {
+        0..100_000 => Some(format!("{number:05}")),
+        100_000..620_000 => {
+            let lead = PACKED_DIGITS[(number / 10_000) as usize];
+            Some(format!("{}{:04}", char::from(lead), number % 10_000))
+        }
+        620_000..15_396_336 => {
+            let mut rest = number - 620_000;
+            let mut digits = [b'0'; 4];
+            for digit in digits.iter_mut().rev() {
+                *digit = PACKED_DIGITS[(rest % 62) as usize];
+                rest /= 62;
+            }
+            Some(format!("~{}", String::from_utf8_lossy(&digits)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_named_numbered() {
+        let eros = Designation::parse("433 Eros (A898 PA)").unwrap();
+
+        assert_eq!(
+            eros,
+            Designation::Numbered {
+                number: 433,
+                name: Some("Eros".to_string()),
+            }
+        );
+        assert_eq!(eros.primary(), "433");
+        assert_eq!(eros.packed().as_deref(), Some("00433"));
+    }
+
+    #[test]
+    fn test_parse_provisional_in_parens() {
+        let designation = Designation::parse("(2010 PK9)").unwrap();
+
+        assert_eq!(designation.primary(), "2010 PK9");
+        assert_eq!(designation.packed().as_deref(), Some("K10P09K"));
+        assert_eq!(
+            Designation::parse("1998 SQ108")
+                .unwrap()
+                .packed()
+                .as_deref(),
+            Some("J98SA8Q")
+        );
+        assert_eq!(
+            Designation::parse("2024 YR4").unwrap().packed().as_deref(),
+            Some("K24Y04R")
+        );
+    }
+
+    #[test]
+    fn test_parse_spk_ids() {
+        assert_eq!(
+            Designation::parse("2000433").unwrap(),
+            Designation::Numbered {
+                number: 433,
+                name: None,
+            }
+        );
+
+        let unnumbered = Designation::parse("3542519").unwrap();
+        assert_eq!(unnumbered, Designation::Spk(3542519));
+        assert_eq!(unnumbered.primary(), "3542519");
+        assert_eq!(unnumbered.packed(), None);
+    }
+
+    #[test]
+    fn test_packed_large_numbers() {
+        let packed = |number| pack_number(number).unwrap();
+
+        assert_eq!(packed(99_999), "99999");
+        assert_eq!(packed(100_001), "A0001");
+        assert_eq!(packed(360_000), "a0000");
+        assert_eq!(packed(620_000), "~0000");
+        assert_eq!(packed(3_140_113), "~AZaz");
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        assert_eq!(Designation::parse("Eros"), None);
+        assert_eq!(Designation::parse(""), None);
+    }
+}
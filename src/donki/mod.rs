@@ -0,0 +1,511 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod test;
+
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, Utc};
+use futures::future;
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+use url::Url;
+
+use crate::keys::KeyRing;
+use crate::request;
+use crate::{Api, ApiError, ApiToken, Clock, StellariaError, StellariaResult, date_serde};
+
+const BASE_URL: &str = "https://api.nasa.gov/DONKI/";
+pub(crate) const PATH: &str = "DONKI/";
+const ENDPOINT: &str = "DONKI";
+/// DONKI timestamps carry no seconds, e.g. `2016-01-01T23:00Z`.
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%MZ";
+
+#[derive(Debug)]
+pub struct DonkiApi {
+    keys: KeyRing,
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+    clock: Clock,
+}
+
+/// The kind of space weather event to list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DonkiEvent {
+    /// Coronal mass ejections.
+    Cme,
+    /// Solar flares.
+    Flr,
+    /// Geomagnetic storms.
+    Gst,
+}
+
+impl DonkiEvent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DonkiEvent::Cme => "CME",
+            DonkiEvent::Flr => "FLR",
+            DonkiEvent::Gst => "GST",
+        }
+    }
+}
+
+/// How far back the builder's default `start_date` reaches, matching NASA's
+/// own default window.
+pub const DEFAULT_WINDOW_DAYS: u64 = 30;
+
+/// Query for one DONKI event type. The builder defaults `start_date` to
+/// [`DEFAULT_WINDOW_DAYS`] before `end_date`, or before today by
+/// [`DonkiApi::params`]'s clock if built from there; NASA defaults a missing
+/// `end_date` to today.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DonkiParams {
+    #[serde(skip)]
+    pub event: DonkiEvent,
+    #[serde(skip_serializing_if = "Option::is_none", with = "date_serde")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "date_serde")]
+    pub end_date: Option<NaiveDate>,
+}
+
+#[derive(Debug)]
+pub struct DonkiParamsBuilder {
+    event: DonkiEvent,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    today: Option<NaiveDate>,
+}
+
+impl DonkiParamsBuilder {
+    pub fn start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn end_date(mut self, end_date: NaiveDate) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    pub(crate) fn today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<DonkiParams> {
+        if let (Some(start_date), Some(end_date)) = (self.start_date, self.end_date)
+            && start_date > end_date
+        {
+            return Err(DonkiError::DonkiParamsError(
+                "Start date cannot be greater than end date".to_string(),
+            )
+            .into());
+        }
+
+        let start_date = self.start_date.unwrap_or_else(|| {
+            let until = self
+                .end_date
+                .or(self.today)
+                .unwrap_or_else(|| Clock::system().today());
+            until - Days::new(DEFAULT_WINDOW_DAYS)
+        });
+        Ok(DonkiParams {
+            event: self.event,
+            start_date: Some(start_date),
+            end_date: self.end_date,
+        })
+    }
+}
+
+impl DonkiParams {
+    pub fn builder(event: DonkiEvent) -> DonkiParamsBuilder {
+        DonkiParamsBuilder {
+            event,
+            start_date: None,
+            end_date: None,
+            today: None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        let event = self.event.as_str();
+        match (self.start_date, self.end_date) {
+            (Some(start_date), Some(end_date)) => {
+                format!("{event} events {start_date} to {end_date}")
+            }
+            (Some(start_date), None) => format!("{event} events from {start_date}"),
+            (None, Some(end_date)) => format!("{event} events until {end_date}"),
+            (None, None) => format!("recent {event} events"),
+        }
+    }
+}
+
+/// The events of the requested [`DonkiEvent`] type.
+#[derive(Debug, Clone)]
+pub enum DonkiResponse {
+    Cme(Vec<CoronalMassEjection>),
+    Flr(Vec<SolarFlare>),
+    Gst(Vec<GeomagneticStorm>),
+}
+
+impl DonkiResponse {
+    fn parse(event: DonkiEvent, body: &[u8]) -> serde_json::Result<Self> {
+        // DONKI answers an empty 200 instead of `[]` when nothing happened.
+        let body = if body.iter().all(u8::is_ascii_whitespace) {
+            b"[]".as_slice()
+        } else {
+            body
+        };
+        Ok(match event {
+            DonkiEvent::Cme => DonkiResponse::Cme(serde_json::from_slice(body)?),
+            DonkiEvent::Flr => DonkiResponse::Flr(serde_json::from_slice(body)?),
+            DonkiEvent::Gst => DonkiResponse::Gst(serde_json::from_slice(body)?),
+        })
+    }
+}
+
+/// One event of any kind, normalized for a merged timeline, see
+/// [`DonkiApi::timeline`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DonkiTimelineEvent {
+    pub kind: DonkiEvent,
+    /// When the event started.
+    pub time: DateTime<Utc>,
+    /// The event's DONKI id, e.g. `2016-01-01T23:00:00-FLR-001`.
+    pub id: String,
+    /// A one-line description, e.g. `M2.3 flare at S21W73`.
+    pub summary: String,
+}
+
+impl DonkiResponse {
+    fn into_timeline(self) -> Vec<DonkiTimelineEvent> {
+        let at = |summary: String, location: Option<String>| match location {
+            Some(location) => format!("{summary} at {location}"),
+            None => summary,
+        };
+        match self {
+            DonkiResponse::Cme(events) => events
+                .into_iter()
+                .map(|cme| {
+                    let speed = cme
+                        .cme_analyses
+                        .iter()
+                        .find(|analysis| analysis.is_most_accurate)
+                        .and_then(|analysis| analysis.speed);
+                    let summary = match speed {
+                        Some(speed) => format!("{speed} km/s CME"),
+                        None => "CME".to_string(),
+                    };
+                    DonkiTimelineEvent {
+                        kind: DonkiEvent::Cme,
+                        time: cme.start_time,
+                        id: cme.activity_id,
+                        summary: at(summary, cme.source_location),
+                    }
+                })
+                .collect(),
+            DonkiResponse::Flr(events) => events
+                .into_iter()
+                .map(|flare| DonkiTimelineEvent {
+                    kind: DonkiEvent::Flr,
+                    time: flare.begin_time,
+                    id: flare.flr_id,
+                    summary: at(format!("{} flare", flare.class_type), flare.source_location),
+                })
+                .collect(),
+            DonkiResponse::Gst(events) => events
+                .into_iter()
+                .map(|storm| {
+                    let max_kp = storm
+                        .all_kp_index
+                        .iter()
+                        .map(|kp| kp.kp_index)
+                        .max_by(f64::total_cmp);
+                    DonkiTimelineEvent {
+                        kind: DonkiEvent::Gst,
+                        time: storm.start_time,
+                        id: storm.gst_id,
+                        summary: match max_kp {
+                            Some(kp) => format!("geomagnetic storm, Kp {kp}"),
+                            None => "geomagnetic storm".to_string(),
+                        },
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CoronalMassEjection {
+    #[serde(rename = "activityID")]
+    pub activity_id: String,
+    pub catalog: String,
+    #[serde(deserialize_with = "deserialize_time")]
+    pub start_time: DateTime<Utc>,
+    pub source_location: Option<String>,
+    pub active_region_num: Option<u32>,
+    pub note: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub cme_analyses: Vec<CmeAnalysis>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+    pub link: Option<Url>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CmeAnalysis {
+    #[serde(
+        rename = "time21_5",
+        deserialize_with = "deserialize_opt_time",
+        default
+    )]
+    pub time_21_5: Option<DateTime<Utc>>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub half_angle: Option<f64>,
+    /// In km/s.
+    pub speed: Option<f64>,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub is_most_accurate: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SolarFlare {
+    #[serde(rename = "flrID")]
+    pub flr_id: String,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub instruments: Vec<Instrument>,
+    #[serde(deserialize_with = "deserialize_time")]
+    pub begin_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "deserialize_opt_time")]
+    pub peak_time: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_opt_time")]
+    pub end_time: Option<DateTime<Utc>>,
+    /// The X-ray class, e.g. `M2.3`.
+    pub class_type: String,
+    pub source_location: Option<String>,
+    pub active_region_num: Option<u32>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+    pub link: Option<Url>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GeomagneticStorm {
+    #[serde(rename = "gstID")]
+    pub gst_id: String,
+    #[serde(deserialize_with = "deserialize_time")]
+    pub start_time: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub all_kp_index: Vec<KpIndex>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub linked_events: Vec<LinkedEvent>,
+    pub link: Option<Url>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KpIndex {
+    #[serde(deserialize_with = "deserialize_time")]
+    pub observed_time: DateTime<Utc>,
+    pub kp_index: f64,
+    pub source: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Instrument {
+    pub display_name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LinkedEvent {
+    #[serde(rename = "activityID")]
+    pub activity_id: String,
+}
+
+fn parse_time(s: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
+    NaiveDateTime::parse_from_str(s, TIME_FORMAT).map(|time| time.and_utc())
+}
+
+fn deserialize_time<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_time(&s).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_opt_time<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = Option::<String>::deserialize(deserializer)?;
+    s.map(|s| parse_time(&s).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+fn null_as_empty<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct DonkiApiError {
+    code: u16,
+    msg: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DonkiError {
+    #[error(transparent)]
+    DonkiApiError(#[from] DonkiApiError),
+    #[error("invalid parameters: {0}")]
+    DonkiParamsError(String),
+}
+
+impl DonkiError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            DonkiError::DonkiApiError(err) => Some(err.code),
+            DonkiError::DonkiParamsError(_) => None,
+        }
+    }
+}
+
+impl DonkiApi {
+    pub fn new(api_key: impl Into<ApiToken>, reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default DONKI base url is valid");
+        Self::with_key_ring(KeyRing::new(api_key.into(), []), reqwest_client, base_url)
+    }
+
+    /// Like [`DonkiApi::new`], but sends requests below `base_url` instead of
+    /// NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(
+        api_key: impl Into<ApiToken>,
+        reqwest_client: reqwest::Client,
+        mut base_url: Url,
+    ) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        Ok(Self::with_key_ring(
+            KeyRing::new(api_key.into(), []),
+            reqwest_client,
+            base_url,
+        ))
+    }
+
+    pub(crate) fn with_key_ring(
+        keys: KeyRing,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> Self {
+        Self {
+            keys,
+            base_url,
+            reqwest_client,
+            clock: Clock::system(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// A params builder whose default window ends today by this API's clock.
+    pub fn params(&self, event: DonkiEvent) -> DonkiParamsBuilder {
+        DonkiParams::builder(event).today(self.clock.today())
+    }
+
+    /// Extra api_keys to rotate to when the active key gets a 429.
+    pub fn with_fallback_keys<T: Into<ApiToken>>(
+        mut self,
+        fallback_keys: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let fallback_keys = fallback_keys.into_iter().map(Into::into);
+        self.keys = KeyRing::new(self.keys.primary().clone(), fallback_keys);
+        self
+    }
+
+    /// Fetches every event type in `kinds` over the window of `params`
+    /// concurrently and merges them into one list, oldest first.
+    ///
+    /// `params.event` is ignored. Fails if any of the requests fails.
+    pub async fn timeline(
+        &self,
+        params: DonkiParams,
+        kinds: &[DonkiEvent],
+    ) -> StellariaResult<Vec<DonkiTimelineEvent>> {
+        let requests = kinds.iter().map(|&event| {
+            self.get(DonkiParams {
+                event,
+                start_date: params.start_date,
+                end_date: params.end_date,
+            })
+        });
+        let mut timeline: Vec<_> = future::try_join_all(requests)
+            .await?
+            .into_iter()
+            .flat_map(DonkiResponse::into_timeline)
+            .collect();
+        timeline.sort_by_key(|event| event.time);
+        Ok(timeline)
+    }
+}
+
+impl Api for DonkiApi {
+    type Params = DonkiParams;
+    type Response = DonkiResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        async {
+            let url = self.base_url.join(params.event.as_str())?;
+            let body = request::get_body(
+                &self.reqwest_client,
+                &url,
+                &params,
+                Some(&self.keys),
+                ENDPOINT,
+                |code, body| DonkiApiError {
+                    code,
+                    msg: request::error_message(body),
+                },
+            )
+            .await?;
+            DonkiResponse::parse(params.event, &body)
+                .map_err(|err| request::json_error(err, body.len()))
+        }
+        .await
+        .map_err(|err| err.with_context(ENDPOINT, params.describe()))
+    }
+}
+
+impl From<DonkiApiError> for StellariaError {
+    fn from(err: DonkiApiError) -> Self {
+        DonkiError::DonkiApiError(err).into()
+    }
+}
+
+impl From<DonkiError> for StellariaError {
+    fn from(err: DonkiError) -> Self {
+        ApiError::DonkiError(err).into()
+    }
+}
@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::Api;
+    use crate::donki::{DonkiApi, DonkiEvent, DonkiParams, DonkiResponse};
+    use crate::test_util::{TEST_KEY, mock_base_url};
+
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use reqwest::Client;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn flr_json() -> serde_json::Value {
+        json!([{
+            "flrID": "2016-01-01T23:00:00-FLR-001",
+            "instruments": [{"displayName": "GOES15: SEM/XRS 1.0-8.0"}],
+            "beginTime": "2016-01-01T23:00Z",
+            "peakTime": "2016-01-02T00:10Z",
+            "endTime": null,
+            "classType": "M2.3",
+            "sourceLocation": "S21W73",
+            "activeRegionNum": 12473,
+            "linkedEvents": [{"activityID": "2016-01-01T23:12:00-CME-001"}],
+            "link": "https://webtools.ccmc.gsfc.nasa.gov/DONKI/view/FLR/10246/-1",
+        }])
+    }
+
+    fn cme_json() -> serde_json::Value {
+        json!([{
+            "activityID": "2016-01-01T23:12:00-CME-001",
+            "catalog": "M2M_CATALOG",
+            "startTime": "2016-01-01T23:12Z",
+            "sourceLocation": "S25W82",
+            "activeRegionNum": 12473,
+            "link": "https://webtools.ccmc.gsfc.nasa.gov/DONKI/view/CME/10247/-1",
+            "note": "Fast CME to the southwest.",
+            "instruments": [{"displayName": "SOHO: LASCO/C2"}],
+            "cmeAnalyses": [{
+                "time21_5": "2016-01-02T02:48Z",
+                "latitude": -20.0,
+                "longitude": 70.0,
+                "halfAngle": 60.0,
+                "speed": 1200.0,
+                "type": "O",
+                "isMostAccurate": true,
+                "note": "",
+            }],
+            "linkedEvents": null,
+        }])
+    }
+
+    fn mock_donki(server: &MockServer) -> DonkiApi {
+        DonkiApi::with_base_url(TEST_KEY, Client::new(), mock_base_url(server, "/DONKI")).unwrap()
+    }
+
+    async fn fetch(
+        server: &MockServer,
+        event: DonkiEvent,
+        body: ResponseTemplate,
+    ) -> DonkiResponse {
+        Mock::given(method("GET"))
+            .and(path(format!("/DONKI/{}", event.as_str())))
+            .and(query_param("startDate", "2016-01-01"))
+            .and(query_param("endDate", "2016-01-02"))
+            .respond_with(body)
+            .expect(1)
+            .mount(server)
+            .await;
+
+        let params = DonkiParams::builder(event)
+            .start_date(NaiveDate::from_ymd_opt(2016, 1, 1).unwrap())
+            .end_date(NaiveDate::from_ymd_opt(2016, 1, 2).unwrap())
+            .build()
+            .unwrap();
+        mock_donki(server).get(params).await.unwrap()
+    }
+
+    // ==================== Timeline Tests ====================
+
+    #[tokio::test]
+    async fn test_timeline_merges_kinds_chronologically() {
+        let server = MockServer::start().await;
+        let mut later_flare = flr_json()[0].clone();
+        later_flare["flrID"] = json!("2016-01-02T04:00:00-FLR-001");
+        later_flare["beginTime"] = json!("2016-01-02T04:00Z");
+        later_flare["classType"] = json!("C1.0");
+        later_flare["sourceLocation"] = json!(null);
+
+        for (event, body) in [
+            (DonkiEvent::Flr, json!([later_flare, flr_json()[0]])),
+            (DonkiEvent::Cme, cme_json()),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/DONKI/{}", event.as_str())))
+                .and(query_param("startDate", "2016-01-01"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let params = DonkiParams::builder(DonkiEvent::Gst)
+            .start_date(NaiveDate::from_ymd_opt(2016, 1, 1).unwrap())
+            .build()
+            .unwrap();
+        let timeline = mock_donki(&server)
+            .timeline(params, &[DonkiEvent::Cme, DonkiEvent::Flr])
+            .await
+            .unwrap();
+
+        let merged: Vec<_> = timeline
+            .iter()
+            .map(|event| (event.kind, event.id.as_str(), event.summary.as_str()))
+            .collect();
+        assert_eq!(
+            merged,
+            [
+                (
+                    DonkiEvent::Flr,
+                    "2016-01-01T23:00:00-FLR-001",
+                    "M2.3 flare at S21W73"
+                ),
+                (
+                    DonkiEvent::Cme,
+                    "2016-01-01T23:12:00-CME-001",
+                    "1200 km/s CME at S25W82"
+                ),
+                (DonkiEvent::Flr, "2016-01-02T04:00:00-FLR-001", "C1.0 flare"),
+            ]
+        );
+        assert_eq!(
+            timeline[1].time,
+            Utc.with_ymd_and_hms(2016, 1, 1, 23, 12, 0).unwrap()
+        );
+    }
+
+    // ==================== Response Tests ====================
+
+    #[tokio::test]
+    async fn test_flr_response_shape() {
+        let server = MockServer::start().await;
+        let body = ResponseTemplate::new(200).set_body_json(flr_json());
+
+        let DonkiResponse::Flr(flares) = fetch(&server, DonkiEvent::Flr, body).await else {
+            panic!("expected solar flares");
+        };
+
+        let flare = &flares[0];
+        assert_eq!(flare.class_type, "M2.3");
+        assert_eq!(
+            flare.begin_time,
+            Utc.with_ymd_and_hms(2016, 1, 1, 23, 0, 0).unwrap()
+        );
+        assert_eq!(flare.end_time, None);
+        assert_eq!(flare.instruments[0].display_name, "GOES15: SEM/XRS 1.0-8.0");
+        assert_eq!(
+            flare.linked_events[0].activity_id,
+            "2016-01-01T23:12:00-CME-001"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cme_response_shape() {
+        let server = MockServer::start().await;
+        let body = ResponseTemplate::new(200).set_body_json(cme_json());
+
+        let DonkiResponse::Cme(cmes) = fetch(&server, DonkiEvent::Cme, body).await else {
+            panic!("expected coronal mass ejections");
+        };
+
+        let cme = &cmes[0];
+        assert_eq!(cme.activity_id, "2016-01-01T23:12:00-CME-001");
+        assert!(cme.linked_events.is_empty());
+        let analysis = &cme.cme_analyses[0];
+        assert_eq!(analysis.speed, Some(1200.0));
+        assert_eq!(analysis.kind, "O");
+        assert!(analysis.is_most_accurate);
+    }
+
+    #[tokio::test]
+    async fn test_empty_body_means_no_events() {
+        let server = MockServer::start().await;
+        let body = ResponseTemplate::new(200).set_body_string("");
+
+        let DonkiResponse::Gst(storms) = fetch(&server, DonkiEvent::Gst, body).await else {
+            panic!("expected geomagnetic storms");
+        };
+
+        assert!(storms.is_empty());
+    }
+
+    // ==================== Params Tests ====================
+
+    #[test]
+    fn test_builder_rejects_reversed_range() {
+        let result = DonkiParams::builder(DonkiEvent::Flr)
+            .start_date(NaiveDate::from_ymd_opt(2016, 1, 2).unwrap())
+            .end_date(NaiveDate::from_ymd_opt(2016, 1, 1).unwrap())
+            .build();
+
+        assert!(result.unwrap_err().is_client_error());
+    }
+
+    #[test]
+    fn test_default_start_date_reaches_back_from_end_date() {
+        let params = DonkiParams::builder(DonkiEvent::Gst)
+            .end_date(NaiveDate::from_ymd_opt(2016, 3, 1).unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(params.start_date, NaiveDate::from_ymd_opt(2016, 1, 31));
+    }
+}
@@ -0,0 +1,365 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+use url::Url;
+
+use crate::request;
+use crate::{Api, ApiError, StellariaError, StellariaResult};
+
+/// EONET lives outside `api.nasa.gov` and needs no api_key.
+const BASE_URL: &str = "https://eonet.gsfc.nasa.gov/api/v3/events";
+const ENDPOINT: &str = "EONET";
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug)]
+pub struct EonetApi {
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+}
+
+/// Whether an event is still ongoing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EonetStatus {
+    Open,
+    Closed,
+}
+
+/// Query for natural events. NASA defaults a missing `status` to open events.
+#[derive(Debug, Default, Serialize)]
+pub struct EonetParams {
+    /// When set, [`EonetApi::get`] also drops any event whose `closed` time
+    /// disagrees with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<EonetStatus>,
+    /// At most this many events, newest first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Only events from the last `days` days, counting today.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
+    /// A category id such as `wildfires` or `severeStorms`, see
+    /// [`EonetCategoryId`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct EonetParamsBuilder {
+    status: Option<EonetStatus>,
+    limit: Option<u32>,
+    days: Option<u32>,
+    last: Option<Duration>,
+    category: Option<String>,
+}
+
+impl EonetParamsBuilder {
+    pub fn status(mut self, status: EonetStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn days(mut self, days: u32) -> Self {
+        self.days = Some(days);
+        self
+    }
+
+    /// Only events from within `window`, rounded up to whole days. Can't be
+    /// combined with [`days`](Self::days).
+    pub fn last(mut self, window: Duration) -> Self {
+        self.last = Some(window);
+        self
+    }
+
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<EonetParams> {
+        let days = match (self.days, self.last) {
+            (Some(_), Some(_)) => {
+                return Err(EonetError::EonetParamsError(
+                    "days and last are mutually exclusive".to_string(),
+                )
+                .into());
+            }
+            (None, Some(window)) => {
+                Some(u32::try_from(window.as_secs().div_ceil(SECONDS_PER_DAY)).unwrap_or(u32::MAX))
+            }
+            (days, None) => days,
+        };
+        if self.limit == Some(0) {
+            return Err(
+                EonetError::EonetParamsError("Limit must be at least 1".to_string()).into(),
+            );
+        }
+        if days == Some(0) {
+            return Err(EonetError::EonetParamsError("Days must be at least 1".to_string()).into());
+        }
+        if self.category.as_deref().is_some_and(str::is_empty) {
+            return Err(
+                EonetError::EonetParamsError("Category cannot be empty".to_string()).into(),
+            );
+        }
+
+        Ok(EonetParams {
+            status: self.status,
+            limit: self.limit,
+            days,
+            category: self.category,
+        })
+    }
+}
+
+impl EonetParams {
+    pub fn builder() -> EonetParamsBuilder {
+        EonetParamsBuilder::default()
+    }
+
+    fn describe(&self) -> String {
+        let status = match self.status {
+            Some(EonetStatus::Open) | None => "open",
+            Some(EonetStatus::Closed) => "closed",
+        };
+        let mut description = match &self.category {
+            Some(category) => format!("{status} {category} events"),
+            None => format!("{status} events"),
+        };
+        if let Some(days) = self.days {
+            description.push_str(&format!(" from the last {days} days"));
+        }
+        description
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EonetResponse {
+    pub title: String,
+    pub description: Option<String>,
+    pub link: Option<Url>,
+    pub events: Vec<EonetEvent>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EonetEvent {
+    /// E.g. `EONET_6455`.
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub link: Option<Url>,
+    /// When the event ended; `None` while it is still open.
+    pub closed: Option<DateTime<Utc>>,
+    pub categories: Vec<Category>,
+    #[serde(default)]
+    pub sources: Vec<Source>,
+    /// Where the event was observed over time, oldest first.
+    pub geometry: Vec<Geometry>,
+}
+
+impl EonetEvent {
+    /// Whether the event is still ongoing, i.e. has no `closed` time yet.
+    pub fn is_open(&self) -> bool {
+        self.closed.is_none()
+    }
+
+    /// Whether the event is filed under the category `id`.
+    pub fn has_category(&self, id: EonetCategoryId) -> bool {
+        self.categories
+            .iter()
+            .any(|category| category.id == id.as_str())
+    }
+
+    /// The url of every source that has one, for linking back to the
+    /// providers.
+    pub fn source_urls(&self) -> Vec<&Url> {
+        self.sources
+            .iter()
+            .filter_map(|source| source.url.as_ref())
+            .collect()
+    }
+}
+
+/// The category ids EONET v3 uses, e.g. `wildfires` or `severeStorms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EonetCategoryId {
+    Drought,
+    DustHaze,
+    Earthquakes,
+    Floods,
+    Landslides,
+    Manmade,
+    SeaLakeIce,
+    SevereStorms,
+    Snow,
+    TempExtremes,
+    Volcanoes,
+    WaterColor,
+    Wildfires,
+}
+
+impl EonetCategoryId {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EonetCategoryId::Drought => "drought",
+            EonetCategoryId::DustHaze => "dustHaze",
+            EonetCategoryId::Earthquakes => "earthquakes",
+            EonetCategoryId::Floods => "floods",
+            EonetCategoryId::Landslides => "landslides",
+            EonetCategoryId::Manmade => "manmade",
+            EonetCategoryId::SeaLakeIce => "seaLakeIce",
+            EonetCategoryId::SevereStorms => "severeStorms",
+            EonetCategoryId::Snow => "snow",
+            EonetCategoryId::TempExtremes => "tempExtremes",
+            EonetCategoryId::Volcanoes => "volcanoes",
+            EonetCategoryId::WaterColor => "waterColor",
+            EonetCategoryId::Wildfires => "wildfires",
+        }
+    }
+}
+
+impl From<EonetCategoryId> for String {
+    fn from(id: EonetCategoryId) -> Self {
+        id.as_str().to_string()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub id: String,
+    /// The originating data provider's page; `None` when missing or not a
+    /// valid url.
+    #[serde(default, deserialize_with = "deserialize_lenient_url")]
+    pub url: Option<Url>,
+}
+
+fn deserialize_lenient_url<'de, D>(deserializer: D) -> Result<Option<Url>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let url = Option::<String>::deserialize(deserializer)?;
+    Ok(url.and_then(|url| Url::parse(url.trim()).ok()))
+}
+
+/// One observation of an event's location.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Geometry {
+    pub date: DateTime<Utc>,
+    /// E.g. wind speed for storms or burned area for wildfires.
+    pub magnitude_value: Option<f64>,
+    pub magnitude_unit: Option<String>,
+    #[serde(flatten)]
+    pub shape: Shape,
+}
+
+/// GeoJSON coordinates, each as `[longitude, latitude]`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum Shape {
+    Point { coordinates: [f64; 2] },
+    Polygon { coordinates: Vec<Vec<[f64; 2]>> },
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct EonetApiError {
+    code: u16,
+    msg: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum EonetError {
+    #[error(transparent)]
+    EonetApiError(#[from] EonetApiError),
+    #[error("invalid parameters: {0}")]
+    EonetParamsError(String),
+}
+
+impl EonetError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            EonetError::EonetApiError(err) => Some(err.code),
+            EonetError::EonetParamsError(_) => None,
+        }
+    }
+}
+
+impl EonetApi {
+    pub fn new(reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default EONET base url is valid");
+        Self {
+            base_url,
+            reqwest_client,
+        }
+    }
+
+    /// Like [`EonetApi::new`], but sends requests to `base_url` instead of
+    /// NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(reqwest_client: reqwest::Client, base_url: Url) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        Ok(Self {
+            base_url,
+            reqwest_client,
+        })
+    }
+}
+
+impl Api for EonetApi {
+    type Params = EonetParams;
+    type Response = EonetResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        let mut response: EonetResponse = request::get_json(
+            &self.reqwest_client,
+            &self.base_url,
+            &params,
+            None,
+            ENDPOINT,
+            |code, body| EonetApiError {
+                code,
+                msg: request::error_message(body),
+            },
+        )
+        .await
+        .map_err(|err| err.with_context(ENDPOINT, params.describe()))?;
+        if let Some(status) = params.status {
+            let open = status == EonetStatus::Open;
+            response.events.retain(|event| event.is_open() == open);
+        }
+        Ok(response)
+    }
+}
+
+impl From<EonetApiError> for StellariaError {
+    fn from(err: EonetApiError) -> Self {
+        EonetError::EonetApiError(err).into()
+    }
+}
+
+impl From<EonetError> for StellariaError {
+    fn from(err: EonetError) -> Self {
+        ApiError::EonetError(err).into()
+    }
+}
@@ -0,0 +1,297 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::Api;
+    use crate::eonet::{
+        Category, EonetApi, EonetCategoryId, EonetParams, EonetResponse, EonetStatus, Shape,
+    };
+    use crate::test_util::mock_base_url;
+
+    use chrono::{TimeZone, Utc};
+    use reqwest::Client;
+    use serde_json::json;
+    use std::time::Duration;
+    use url::Url;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn events_json() -> serde_json::Value {
+        json!({
+            "title": "EONET Events",
+            "description": "Natural events from EONET.",
+            "link": "https://eonet.gsfc.nasa.gov/api/v3/events",
+            "events": [
+                {
+                    "id": "EONET_6455",
+                    "title": "Sugar Fire",
+                    "description": null,
+                    "link": "https://eonet.gsfc.nasa.gov/api/v3/events/EONET_6455",
+                    "closed": null,
+                    "categories": [{"id": "wildfires", "title": "Wildfires"}],
+                    "sources": [{"id": "InciWeb", "url": "https://inciweb.nwcg.gov/incident/7704/"}],
+                    "geometry": [{
+                        "magnitudeValue": 1250.0,
+                        "magnitudeUnit": "acres",
+                        "date": "2021-07-02T14:00:00Z",
+                        "type": "Point",
+                        "coordinates": [-120.4, 39.9],
+                    }],
+                },
+                {
+                    "id": "EONET_5920",
+                    "title": "Iceberg D28",
+                    "description": null,
+                    "link": "https://eonet.gsfc.nasa.gov/api/v3/events/EONET_5920",
+                    "closed": "2021-06-30T00:00:00Z",
+                    "categories": [{"id": "seaLakeIce", "title": "Sea and Lake Ice"}],
+                    "sources": [],
+                    "geometry": [{
+                        "magnitudeValue": null,
+                        "magnitudeUnit": null,
+                        "date": "2021-06-01T00:00:00Z",
+                        "type": "Polygon",
+                        "coordinates": [[[-70.0, -60.0], [-69.0, -60.0], [-69.0, -61.0], [-70.0, -60.0]]],
+                    }],
+                },
+            ],
+        })
+    }
+
+    fn mock_eonet(server: &MockServer) -> EonetApi {
+        EonetApi::with_base_url(Client::new(), mock_base_url(server, "/api/v3/events")).unwrap()
+    }
+
+    // ==================== Query Tests ====================
+
+    #[tokio::test]
+    async fn test_filters_serialize_to_query() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/events"))
+            .and(query_param("status", "closed"))
+            .and(query_param("limit", "5"))
+            .and(query_param("days", "20"))
+            .and(query_param("category", "wildfires"))
+            .and(query_param_is_missing("api_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(events_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = EonetParams::builder()
+            .status(EonetStatus::Closed)
+            .limit(5)
+            .days(20)
+            .category(EonetCategoryId::Wildfires)
+            .build()
+            .unwrap();
+        mock_eonet(&server).get(params).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unset_filters_are_omitted() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/events"))
+            .and(query_param_is_missing("status"))
+            .and(query_param_is_missing("limit"))
+            .and(query_param_is_missing("days"))
+            .and(query_param_is_missing("category"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(events_json()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = EonetParams::builder().build().unwrap();
+        mock_eonet(&server).get(params).await.unwrap();
+    }
+
+    // ==================== Response Tests ====================
+
+    #[tokio::test]
+    async fn test_events_response_shape() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/events"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(events_json()))
+            .mount(&server)
+            .await;
+
+        let response = mock_eonet(&server)
+            .get(EonetParams::default())
+            .await
+            .unwrap();
+
+        let fire = &response.events[0];
+        assert_eq!(fire.id, "EONET_6455");
+        assert_eq!(fire.closed, None);
+        assert_eq!(
+            fire.categories,
+            [Category {
+                id: "wildfires".to_string(),
+                title: "Wildfires".to_string(),
+            }]
+        );
+        assert_eq!(fire.sources[0].id, "InciWeb");
+        let observation = &fire.geometry[0];
+        assert_eq!(
+            observation.date,
+            Utc.with_ymd_and_hms(2021, 7, 2, 14, 0, 0).unwrap()
+        );
+        assert_eq!(observation.magnitude_value, Some(1250.0));
+        assert_eq!(observation.magnitude_unit.as_deref(), Some("acres"));
+        assert_eq!(
+            observation.shape,
+            Shape::Point {
+                coordinates: [-120.4, 39.9]
+            }
+        );
+
+        let iceberg = &response.events[1];
+        assert_eq!(
+            iceberg.closed,
+            Some(Utc.with_ymd_and_hms(2021, 6, 30, 0, 0, 0).unwrap())
+        );
+        let Shape::Polygon { coordinates } = &iceberg.geometry[0].shape else {
+            panic!("expected a polygon");
+        };
+        assert_eq!(coordinates[0].len(), 4);
+        assert_eq!(coordinates[0][1], [-69.0, -60.0]);
+    }
+
+    #[test]
+    fn test_open_and_closed_events() {
+        let response: EonetResponse = serde_json::from_value(events_json()).unwrap();
+
+        let fire = &response.events[0];
+        assert!(fire.is_open());
+        let iceberg = &response.events[1];
+        assert!(!iceberg.is_open());
+    }
+
+    #[tokio::test]
+    async fn test_status_filter_matches_closed_time() {
+        let server = MockServer::start().await;
+
+        for status in ["open", "closed"] {
+            Mock::given(method("GET"))
+                .and(path("/api/v3/events"))
+                .and(query_param("status", status))
+                .respond_with(ResponseTemplate::new(200).set_body_json(events_json()))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let eonet = mock_eonet(&server);
+        let params = |status| EonetParams::builder().status(status).build().unwrap();
+        let closed = eonet.get(params(EonetStatus::Closed)).await.unwrap();
+        let open = eonet.get(params(EonetStatus::Open)).await.unwrap();
+
+        assert_eq!(closed.events.len(), 1);
+        assert!(closed.events.iter().all(|event| event.closed.is_some()));
+        assert_eq!(open.events.len(), 1);
+        assert!(open.events.iter().all(|event| event.closed.is_none()));
+    }
+
+    #[test]
+    fn test_category_membership() {
+        let mut events = events_json();
+        events["events"][0]["categories"] = json!([
+            {"id": "wildfires", "title": "Wildfires"},
+            {"id": "dustHaze", "title": "Dust and Haze"},
+        ]);
+        let response: EonetResponse = serde_json::from_value(events).unwrap();
+
+        let fire = &response.events[0];
+        assert!(fire.has_category(EonetCategoryId::Wildfires));
+        assert!(fire.has_category(EonetCategoryId::DustHaze));
+        assert!(!fire.has_category(EonetCategoryId::SeaLakeIce));
+        let iceberg = &response.events[1];
+        assert!(iceberg.has_category(EonetCategoryId::SeaLakeIce));
+        assert!(!iceberg.has_category(EonetCategoryId::Wildfires));
+    }
+
+    #[test]
+    fn test_source_urls_skip_unusable_urls() {
+        let mut events = events_json();
+        events["events"][0]["sources"] = json!([
+            {"id": "InciWeb", "url": "https://inciweb.nwcg.gov/incident/7704/"},
+            {"id": "CALFIRE", "url": ""},
+            {"id": "IRWIN"},
+            {"id": "GDACS", "url": "https://www.gdacs.org/report.aspx?eventid=1000818"},
+        ]);
+        let response: EonetResponse = serde_json::from_value(events).unwrap();
+
+        let fire = &response.events[0];
+        assert_eq!(fire.sources.len(), 4);
+        assert_eq!(fire.sources[1].url, None);
+        let urls: Vec<_> = fire.source_urls().into_iter().map(Url::as_str).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://inciweb.nwcg.gov/incident/7704/",
+                "https://www.gdacs.org/report.aspx?eventid=1000818",
+            ]
+        );
+        assert!(response.events[1].source_urls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_error_status_names_request() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v3/events"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("down for maintenance"))
+            .mount(&server)
+            .await;
+
+        let params = EonetParams::builder()
+            .category("volcanoes")
+            .days(7)
+            .build()
+            .unwrap();
+        let err = mock_eonet(&server).get(params).await.unwrap_err();
+
+        assert_eq!(err.status(), Some(503));
+        assert_eq!(
+            err.to_string(),
+            "while fetching EONET for open volcanoes events from the last 7 days: \
+             http code 503: down for maintenance"
+        );
+    }
+
+    // ==================== Params Tests ====================
+
+    #[test]
+    fn test_builder_rejects_zero_limit_and_days() {
+        let limit = EonetParams::builder().limit(0).build();
+        let days = EonetParams::builder().days(0).build();
+
+        assert!(limit.unwrap_err().is_client_error());
+        assert!(days.unwrap_err().is_client_error());
+    }
+
+    #[test]
+    fn test_last_rounds_up_to_whole_days() {
+        let days = |window| EonetParams::builder().last(window).build().unwrap().days;
+
+        assert_eq!(days(Duration::from_secs(24 * 60 * 60)), Some(1));
+        assert_eq!(days(Duration::from_secs(36 * 60 * 60)), Some(2));
+        assert_eq!(days(Duration::from_secs(1)), Some(1));
+        assert_eq!(EonetParams::builder().build().unwrap().days, None);
+
+        let zero = EonetParams::builder().last(Duration::ZERO).build();
+        assert!(zero.unwrap_err().is_client_error());
+        let both = EonetParams::builder()
+            .days(3)
+            .last(Duration::from_secs(60))
+            .build();
+        assert!(both.unwrap_err().is_client_error());
+    }
+}
@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod test;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+use url::Url;
+
+use crate::clock::{OutsideWindow, check_date_window};
+use crate::geo::LatLon;
+use crate::keys::KeyRing;
+use crate::request;
+use crate::{Api, ApiError, ApiToken, Clock, StellariaError, StellariaResult};
+
+const BASE_URL: &str = "https://api.nasa.gov/EPIC/api/";
+pub(crate) const PATH: &str = "EPIC/api/";
+const ENDPOINT: &str = "EPIC";
+/// EPIC's public image archive, which needs no api_key.
+const ARCHIVE_BASE: &str = "https://epic.gsfc.nasa.gov/archive/";
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The first day EPIC imagery is available.
+pub const EPIC_EPOCH: NaiveDate = NaiveDate::from_ymd_opt(2015, 6, 13).unwrap();
+
+#[derive(Debug)]
+pub struct EpicApi {
+    keys: KeyRing,
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+    clock: Clock,
+}
+
+/// Which EPIC image set to query.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EpicCollection {
+    /// True-color images as the camera sees them.
+    #[default]
+    Natural,
+    /// Color-corrected images that bring out land features.
+    Enhanced,
+}
+
+impl EpicCollection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EpicCollection::Natural => "natural",
+            EpicCollection::Enhanced => "enhanced",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EpicParams {
+    pub collection: EpicCollection,
+    /// The day to list; NASA returns the most recent day when unset.
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Default)]
+pub struct EpicParamsBuilder {
+    collection: EpicCollection,
+    date: Option<NaiveDate>,
+    today: Option<NaiveDate>,
+}
+
+impl EpicParamsBuilder {
+    pub fn collection(mut self, collection: EpicCollection) -> Self {
+        self.collection = collection;
+        self
+    }
+
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    pub(crate) fn today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<EpicParams> {
+        let params = EpicParams {
+            collection: self.collection,
+            date: self.date,
+        };
+        params.validate(self.today.unwrap_or_else(|| Clock::system().today()))?;
+        Ok(params)
+    }
+}
+
+impl EpicParams {
+    pub fn builder() -> EpicParamsBuilder {
+        EpicParamsBuilder::default()
+    }
+
+    /// Fails for a date EPIC can't have imagery for. [`EpicApi::get`] checks
+    /// this too, since the fields can be set without the builder.
+    fn validate(&self, today: NaiveDate) -> StellariaResult<()> {
+        match self.date {
+            Some(date) => validate_date(date, today),
+            None => Ok(()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.date {
+            Some(date) => format!("{} images on {date}", self.collection.as_str()),
+            None => format!("latest {} images", self.collection.as_str()),
+        }
+    }
+
+    fn path(&self) -> String {
+        match self.date {
+            Some(date) => format!("{}/date/{date}", self.collection.as_str()),
+            None => self.collection.as_str().to_string(),
+        }
+    }
+}
+
+/// Metadata of the images EPIC took on one day.
+pub type EpicResponse = Vec<EpicImage>;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EpicImage {
+    pub identifier: String,
+    /// The archive file name without extension, e.g. `epic_1b_20151031074844`.
+    pub image: String,
+    #[serde(deserialize_with = "deserialize_datetime")]
+    pub date: NaiveDateTime,
+    pub caption: String,
+    /// The point on Earth at the center of the image.
+    pub centroid_coordinates: LatLon,
+}
+
+impl EpicImage {
+    /// The full-resolution PNG in EPIC's public archive, whose path embeds
+    /// the collection and the capture date.
+    pub fn archive_url(&self, collection: EpicCollection) -> Url {
+        let date = self.date.date();
+        let path = format!(
+            "{}/{:04}/{:02}/{:02}/png/{}.png",
+            collection.as_str(),
+            date.year(),
+            date.month(),
+            date.day(),
+            self.image,
+        );
+        Url::parse(ARCHIVE_BASE)
+            .and_then(|base| base.join(&path))
+            .expect("EPIC archive url is valid")
+    }
+}
+
+fn validate_date(date: NaiveDate, today: NaiveDate) -> StellariaResult<()> {
+    match check_date_window(date, EPIC_EPOCH, today) {
+        Ok(()) => Ok(()),
+        Err(OutsideWindow::BeforeEpoch) => Err(EpicError::EpicParamsError(format!(
+            "EPIC imagery starts on {EPIC_EPOCH}, got {date}"
+        ))
+        .into()),
+        Err(OutsideWindow::AfterToday) => Err(EpicError::FutureDate { date, today }.into()),
+    }
+}
+
+fn deserialize_datetime<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    NaiveDateTime::parse_from_str(&s, DATETIME_FORMAT).map_err(serde::de::Error::custom)
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct EpicApiError {
+    code: u16,
+    msg: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum EpicError {
+    #[error(transparent)]
+    EpicApiError(#[from] EpicApiError),
+    #[error("invalid parameters: {0}")]
+    EpicParamsError(String),
+    #[error("EPIC has no imagery for {date} yet, today is {today}")]
+    FutureDate { date: NaiveDate, today: NaiveDate },
+}
+
+impl EpicError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            EpicError::EpicApiError(err) => Some(err.code),
+            EpicError::EpicParamsError(_) | EpicError::FutureDate { .. } => None,
+        }
+    }
+}
+
+impl EpicApi {
+    pub fn new(api_key: impl Into<ApiToken>, reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default EPIC base url is valid");
+        Self::with_key_ring(KeyRing::new(api_key.into(), []), reqwest_client, base_url)
+    }
+
+    /// Like [`EpicApi::new`], but sends requests below `base_url` instead of
+    /// NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(
+        api_key: impl Into<ApiToken>,
+        reqwest_client: reqwest::Client,
+        mut base_url: Url,
+    ) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        Ok(Self::with_key_ring(
+            KeyRing::new(api_key.into(), []),
+            reqwest_client,
+            base_url,
+        ))
+    }
+
+    pub(crate) fn with_key_ring(
+        keys: KeyRing,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> Self {
+        Self {
+            keys,
+            base_url,
+            reqwest_client,
+            clock: Clock::system(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// A params builder whose "today" comes from this API's clock.
+    pub fn params(&self) -> EpicParamsBuilder {
+        EpicParams::builder().today(self.clock.today())
+    }
+
+    /// Extra api_keys to rotate to when the active key gets a 429.
+    pub fn with_fallback_keys<T: Into<ApiToken>>(
+        mut self,
+        fallback_keys: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let fallback_keys = fallback_keys.into_iter().map(Into::into);
+        self.keys = KeyRing::new(self.keys.primary().clone(), fallback_keys);
+        self
+    }
+
+    /// Every day `collection` has imagery for, oldest first.
+    pub async fn available(&self, collection: EpicCollection) -> StellariaResult<Vec<NaiveDate>> {
+        async {
+            let url = self
+                .base_url
+                .join(&format!("{}/available", collection.as_str()))?;
+            let mut dates: Vec<NaiveDate> = request::get_json(
+                &self.reqwest_client,
+                &url,
+                &(),
+                Some(&self.keys),
+                ENDPOINT,
+                |code, body| EpicApiError {
+                    code,
+                    msg: request::error_message(body),
+                },
+            )
+            .await?;
+            dates.sort_unstable();
+            StellariaResult::Ok(dates)
+        }
+        .await
+        .map_err(|err| {
+            err.with_context(ENDPOINT, format!("available {} dates", collection.as_str()))
+        })
+    }
+
+    /// Whether `collection` has imagery for `date`. Like [`Api::get`], fails
+    /// without a request for a date before [`EPIC_EPOCH`] or after today.
+    pub async fn is_available(
+        &self,
+        collection: EpicCollection,
+        date: NaiveDate,
+    ) -> StellariaResult<bool> {
+        validate_date(date, self.clock.today())
+            .map_err(|err| err.with_context(ENDPOINT, format!("availability of {date}")))?;
+        let dates = self.available(collection).await?;
+        Ok(dates.binary_search(&date).is_ok())
+    }
+}
+
+impl Api for EpicApi {
+    type Params = EpicParams;
+    type Response = EpicResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        async {
+            params.validate(self.clock.today())?;
+            let url = self.base_url.join(&params.path())?;
+            request::get_json(
+                &self.reqwest_client,
+                &url,
+                &(),
+                Some(&self.keys),
+                ENDPOINT,
+                |code, body| EpicApiError {
+                    code,
+                    msg: request::error_message(body),
+                },
+            )
+            .await
+        }
+        .await
+        .map_err(|err| err.with_context(ENDPOINT, params.describe()))
+    }
+}
+
+impl From<EpicApiError> for StellariaError {
+    fn from(err: EpicApiError) -> Self {
+        EpicError::EpicApiError(err).into()
+    }
+}
+
+impl From<EpicError> for StellariaError {
+    fn from(err: EpicError) -> Self {
+        ApiError::EpicError(err).into()
+    }
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::epic::{EpicApi, EpicCollection, EpicError, EpicImage, EpicParams};
+    use crate::geo::LatLon;
+    use crate::test_util::{TEST_KEY, mock_base_url};
+    use crate::{Api, ApiError, Clock, StellariaError};
+
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use reqwest::Client;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn image_json() -> serde_json::Value {
+        json!({
+            "identifier": "20151031003633",
+            "caption": "This image was taken by NASA's EPIC camera onboard the NOAA DSCOVR spacecraft",
+            "image": "epic_1b_20151031003633",
+            "version": "03",
+            "centroid_coordinates": {"lat": -10.58203, "lon": 163.660156},
+            "date": "2015-10-31 00:36:33",
+        })
+    }
+
+    // ==================== Centroid Tests ====================
+
+    #[test]
+    fn test_centroid_parses_into_lat_lon() {
+        let image: EpicImage = serde_json::from_value(image_json()).unwrap();
+        assert_eq!(
+            image.centroid_coordinates,
+            LatLon::new(-10.58203, 163.660156).unwrap()
+        );
+
+        let mut off_map = image_json();
+        off_map["centroid_coordinates"]["lat"] = json!(95.0);
+        let err = serde_json::from_value::<EpicImage>(off_map).unwrap_err();
+        assert!(err.to_string().contains("latitude 95"));
+    }
+
+    // ==================== Archive URL Tests ====================
+
+    #[test]
+    fn test_archive_url_embeds_collection_and_date() {
+        let image: EpicImage = serde_json::from_value(image_json()).unwrap();
+
+        assert_eq!(
+            image.archive_url(EpicCollection::Natural).as_str(),
+            "https://epic.gsfc.nasa.gov/archive/natural/2015/10/31/png/epic_1b_20151031003633.png"
+        );
+        assert_eq!(
+            image.archive_url(EpicCollection::Enhanced).as_str(),
+            "https://epic.gsfc.nasa.gov/archive/enhanced/2015/10/31/png/epic_1b_20151031003633.png"
+        );
+    }
+
+    // ==================== Params Tests ====================
+
+    #[test]
+    fn test_builder_rejects_dates_before_epic() {
+        let err = EpicParams::builder()
+            .date(NaiveDate::from_ymd_opt(2015, 6, 12).unwrap())
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(err, StellariaError::ApiError(_)));
+        assert!(err.is_client_error());
+    }
+
+    #[test]
+    fn test_builder_rejects_future_dates() {
+        let today = NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+        let err = EpicParams::builder()
+            .today(today)
+            .date(NaiveDate::from_ymd_opt(2024, 12, 13).unwrap())
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            StellariaError::ApiError(ApiError::EpicError(EpicError::FutureDate { .. }))
+        ));
+        assert!(err.is_client_error());
+
+        let params = EpicParams::builder()
+            .today(today)
+            .date(today)
+            .build()
+            .unwrap();
+        assert_eq!(params.date, Some(today));
+    }
+
+    // ==================== API Tests ====================
+
+    #[tokio::test]
+    async fn test_get_enhanced_on_date() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/EPIC/api/enhanced/date/2015-10-31"))
+            .and(query_param("api_key", "TEST_KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([image_json()])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let epic =
+            EpicApi::with_base_url(TEST_KEY, Client::new(), mock_base_url(&server, "/EPIC/api"))
+                .unwrap();
+        let params = EpicParams::builder()
+            .collection(EpicCollection::Enhanced)
+            .date(NaiveDate::from_ymd_opt(2015, 10, 31).unwrap())
+            .build()
+            .unwrap();
+        let images = epic.get(params).await.unwrap();
+
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].identifier, "20151031003633");
+    }
+
+    #[tokio::test]
+    async fn test_get_rejects_hand_built_dates_outside_archive() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let now = Utc.with_ymd_and_hms(2024, 12, 12, 12, 0, 0).unwrap();
+        let epic =
+            EpicApi::with_base_url(TEST_KEY, Client::new(), mock_base_url(&server, "/EPIC/api"))
+                .unwrap()
+                .with_clock(Clock::fixed(now));
+
+        for date in [(2024, 12, 13), (2015, 6, 12)] {
+            let params = EpicParams {
+                date: NaiveDate::from_ymd_opt(date.0, date.1, date.2),
+                ..Default::default()
+            };
+            let err = epic.get(params).await.unwrap_err();
+            assert!(err.is_client_error(), "{err}");
+        }
+    }
+
+    // ==================== Available Dates Tests ====================
+
+    #[tokio::test]
+    async fn test_available_lists_dates_in_order() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/EPIC/api/enhanced/available"))
+            .and(query_param("api_key", TEST_KEY))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!(["2015-08-01", "2015-06-13"])),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let epic =
+            EpicApi::with_base_url(TEST_KEY, Client::new(), mock_base_url(&server, "/EPIC/api"))
+                .unwrap();
+        let dates = epic.available(EpicCollection::Enhanced).await.unwrap();
+
+        assert_eq!(
+            dates,
+            [
+                NaiveDate::from_ymd_opt(2015, 6, 13).unwrap(),
+                NaiveDate::from_ymd_opt(2015, 8, 1).unwrap(),
+            ]
+        );
+        let missing = NaiveDate::from_ymd_opt(2015, 7, 1).unwrap();
+        let available = epic
+            .is_available(EpicCollection::Enhanced, missing)
+            .await
+            .unwrap();
+        assert!(!available);
+    }
+
+    #[tokio::test]
+    async fn test_is_available_rejects_dates_outside_archive() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let now = Utc.with_ymd_and_hms(2024, 12, 12, 12, 0, 0).unwrap();
+        let epic =
+            EpicApi::with_base_url(TEST_KEY, Client::new(), mock_base_url(&server, "/EPIC/api"))
+                .unwrap()
+                .with_clock(Clock::fixed(now));
+
+        let future = NaiveDate::from_ymd_opt(2024, 12, 13).unwrap();
+        let err = epic
+            .is_available(EpicCollection::Natural, future)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.root(),
+            StellariaError::ApiError(ApiError::EpicError(EpicError::FutureDate { .. }))
+        ));
+
+        let too_early = NaiveDate::from_ymd_opt(2015, 6, 12).unwrap();
+        let err = epic
+            .is_available(EpicCollection::Natural, too_early)
+            .await
+            .unwrap_err();
+        assert!(err.is_client_error(), "{err}");
+    }
+}
@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod test;
+
+use serde::{Deserialize, Serialize, Serializer};
+use thiserror::Error;
+use url::Url;
+
+use crate::request;
+use crate::{Api, ApiError, StellariaError, StellariaResult};
+
+/// The Exoplanet Archive's TAP service lives at Caltech and needs no api_key.
+const BASE_URL: &str = "https://exoplanetarchive.ipac.caltech.edu/TAP/sync";
+const ENDPOINT: &str = "Exoplanet Archive";
+
+#[derive(Debug)]
+pub struct ExoplanetApi {
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+}
+
+/// An ADQL query against one archive table, e.g. `ps` (planetary systems)
+/// or `pscomppars` (one composite row per planet).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExoplanetParams {
+    pub table: String,
+    /// The columns to return; `*` for all of them.
+    pub select: String,
+    /// An ADQL condition such as `disc_year = 2020`.
+    pub where_clause: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ExoplanetParamsBuilder {
+    table: String,
+    select: Option<String>,
+    where_clause: Option<String>,
+}
+
+impl ExoplanetParamsBuilder {
+    pub fn select(mut self, select: impl Into<String>) -> Self {
+        self.select = Some(select.into());
+        self
+    }
+
+    pub fn where_clause(mut self, where_clause: impl Into<String>) -> Self {
+        self.where_clause = Some(where_clause.into());
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<ExoplanetParams> {
+        let table = self.table.trim();
+        if table.is_empty() {
+            return Err(ExoplanetError::ExoplanetParamsError(
+                "Table name cannot be empty".to_string(),
+            )
+            .into());
+        }
+        if table.contains(char::is_whitespace) {
+            return Err(ExoplanetError::ExoplanetParamsError(format!(
+                "Table name cannot contain whitespace, got {table:?}"
+            ))
+            .into());
+        }
+
+        Ok(ExoplanetParams {
+            table: table.to_string(),
+            select: self.select.unwrap_or_else(|| "*".to_string()),
+            where_clause: self.where_clause,
+        })
+    }
+}
+
+impl ExoplanetParams {
+    pub fn builder(table: impl Into<String>) -> ExoplanetParamsBuilder {
+        ExoplanetParamsBuilder {
+            table: table.into(),
+            select: None,
+            where_clause: None,
+        }
+    }
+
+    /// The ADQL sent as the `query` parameter.
+    pub fn adql(&self) -> String {
+        match &self.where_clause {
+            Some(where_clause) => format!(
+                "select {} from {} where {}",
+                self.select, self.table, where_clause
+            ),
+            None => format!("select {} from {}", self.select, self.table),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match &self.where_clause {
+            Some(where_clause) => format!("{} where {}", self.table, where_clause),
+            None => format!("all of {}", self.table),
+        }
+    }
+}
+
+impl Serialize for ExoplanetParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [("query", self.adql().as_str()), ("format", "json")].serialize(serializer)
+    }
+}
+
+/// The result rows, one JSON object per row keyed by column name. Columns
+/// differ between tables and queries, so they are left untyped.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct ExoplanetResponse {
+    pub rows: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct ExoplanetApiError {
+    code: u16,
+    msg: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExoplanetError {
+    #[error(transparent)]
+    ExoplanetApiError(#[from] ExoplanetApiError),
+    #[error("invalid parameters: {0}")]
+    ExoplanetParamsError(String),
+}
+
+impl ExoplanetError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ExoplanetError::ExoplanetApiError(err) => Some(err.code),
+            ExoplanetError::ExoplanetParamsError(_) => None,
+        }
+    }
+}
+
+impl ExoplanetApi {
+    pub fn new(reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default Exoplanet Archive base url is valid");
+        Self {
+            base_url,
+            reqwest_client,
+        }
+    }
+
+    /// Like [`ExoplanetApi::new`], but sends requests to `base_url` instead of
+    /// the archive, e.g. a mock server or a proxy.
+    pub fn with_base_url(reqwest_client: reqwest::Client, base_url: Url) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        Ok(Self {
+            base_url,
+            reqwest_client,
+        })
+    }
+}
+
+impl Api for ExoplanetApi {
+    type Params = ExoplanetParams;
+    type Response = ExoplanetResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        request::get_json(
+            &self.reqwest_client,
+            &self.base_url,
+            &params,
+            None,
+            ENDPOINT,
+            |code, body| ExoplanetApiError {
+                code,
+                msg: request::error_message(body),
+            },
+        )
+        .await
+        .map_err(|err| err.with_context(ENDPOINT, params.describe()))
+    }
+}
+
+impl From<ExoplanetApiError> for StellariaError {
+    fn from(err: ExoplanetApiError) -> Self {
+        ExoplanetError::ExoplanetApiError(err).into()
+    }
+}
+
+impl From<ExoplanetError> for StellariaError {
+    fn from(err: ExoplanetError) -> Self {
+        ApiError::ExoplanetError(err).into()
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::Api;
+    use crate::exoplanet::{ExoplanetApi, ExoplanetParams};
+    use crate::test_util::mock_base_url;
+
+    use reqwest::Client;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_exoplanet(server: &MockServer) -> ExoplanetApi {
+        ExoplanetApi::with_base_url(Client::new(), mock_base_url(server, "/TAP/sync")).unwrap()
+    }
+
+    // ==================== Query Tests ====================
+
+    #[test]
+    fn test_adql_from_builder() {
+        let params = ExoplanetParams::builder("ps")
+            .select("pl_name,hostname")
+            .where_clause("disc_year = 2020")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            params.adql(),
+            "select pl_name,hostname from ps where disc_year = 2020"
+        );
+    }
+
+    #[test]
+    fn test_adql_defaults_to_all_columns() {
+        let params = ExoplanetParams::builder("pscomppars").build().unwrap();
+
+        assert_eq!(params.adql(), "select * from pscomppars");
+    }
+
+    #[tokio::test]
+    async fn test_query_url_and_rows() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/TAP/sync"))
+            .and(query_param(
+                "query",
+                "select pl_name,disc_year from ps where disc_year > 2020",
+            ))
+            .and(query_param("format", "json"))
+            .and(query_param_is_missing("api_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"pl_name": "TOI-700 e", "disc_year": 2023},
+                {"pl_name": "LP 791-18 d", "disc_year": 2023},
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = ExoplanetParams::builder("ps")
+            .select("pl_name,disc_year")
+            .where_clause("disc_year > 2020")
+            .build()
+            .unwrap();
+        let response = mock_exoplanet(&server).get(params).await.unwrap();
+
+        assert_eq!(response.rows.len(), 2);
+        assert_eq!(response.rows[0]["pl_name"], "TOI-700 e");
+        assert_eq!(response.rows[1]["disc_year"], 2023);
+    }
+
+    #[tokio::test]
+    async fn test_error_status_names_table() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/TAP/sync"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("unknown table"))
+            .mount(&server)
+            .await;
+
+        let params = ExoplanetParams::builder("nope").build().unwrap();
+        let err = mock_exoplanet(&server).get(params).await.unwrap_err();
+
+        assert_eq!(err.status(), Some(400));
+        assert_eq!(
+            err.to_string(),
+            "while fetching Exoplanet Archive for all of nope: http code 400: unknown table"
+        );
+    }
+
+    // ==================== Params Tests ====================
+
+    #[test]
+    fn test_builder_rejects_empty_table() {
+        let empty = ExoplanetParams::builder("").build();
+        let blank = ExoplanetParams::builder("   ").build();
+
+        assert!(empty.unwrap_err().is_client_error());
+        assert!(blank.unwrap_err().is_client_error());
+    }
+
+    #[test]
+    fn test_builder_rejects_table_with_whitespace() {
+        let result = ExoplanetParams::builder("ps where 1=1").build();
+
+        assert!(result.unwrap_err().is_client_error());
+    }
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Geographic coordinates shared by the APIs that report them.
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// A point on Earth in degrees, checked to lie within the valid ranges.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(try_from = "RawLatLon")]
+pub struct LatLon {
+    lat: f64,
+    lon: f64,
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum LatLonError {
+    #[error("latitude {0} is outside [-90, 90]")]
+    Latitude(f64),
+    #[error("longitude {0} is outside [-180, 180]")]
+    Longitude(f64),
+}
+
+impl LatLon {
+    pub fn new(lat: f64, lon: f64) -> Result<Self, LatLonError> {
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err(LatLonError::Latitude(lat));
+        }
+        if !(-180.0..=180.0).contains(&lon) {
+            return Err(LatLonError::Longitude(lon));
+        }
+        Ok(Self { lat, lon })
+    }
+
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    pub fn lon(&self) -> f64 {
+        self.lon
+    }
+}
+
+#[derive(Deserialize)]
+struct RawLatLon {
+    lat: f64,
+    lon: f64,
+}
+
+impl TryFrom<RawLatLon> for LatLon {
+    type Error = LatLonError;
+
+    fn try_from(raw: RawLatLon) -> Result<Self, Self::Error> {
+        Self::new(raw.lat, raw.lon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_checks_ranges() {
+        assert_eq!(LatLon::new(90.0, -180.0).unwrap().lat(), 90.0);
+        assert_eq!(
+            LatLon::new(-90.5, 0.0).unwrap_err(),
+            LatLonError::Latitude(-90.5)
+        );
+        assert_eq!(
+            LatLon::new(0.0, 180.5).unwrap_err(),
+            LatLonError::Longitude(180.5)
+        );
+        assert!(LatLon::new(f64::NAN, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rejects_out_of_range() {
+        let point: LatLon = serde_json::from_str(r#"{"lat": 12.5, "lon": -45.0}"#).unwrap();
+        assert_eq!((point.lat(), point.lon()), (12.5, -45.0));
+
+        let err = serde_json::from_str::<LatLon>(r#"{"lat": 12.5, "lon": 200.0}"#).unwrap_err();
+        assert!(err.to_string().contains("longitude 200"));
+    }
+}
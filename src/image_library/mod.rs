@@ -0,0 +1,361 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod test;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize, Serializer};
+use thiserror::Error;
+use url::Url;
+
+use crate::pagination::{Page, paginate};
+use crate::request;
+use crate::{Api, ApiError, StellariaError, StellariaResult};
+
+/// The library lives outside `api.nasa.gov` and needs no api_key.
+const BASE_URL: &str = "https://images-api.nasa.gov/search";
+const ENDPOINT: &str = "Image Library";
+
+/// How many items NASA returns per search page.
+pub const PAGE_SIZE: usize = 100;
+
+#[derive(Debug)]
+pub struct ImageLibraryApi {
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum LibraryMediaType {
+    Image,
+    Video,
+    Audio,
+}
+
+impl LibraryMediaType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LibraryMediaType::Image => "image",
+            LibraryMediaType::Video => "video",
+            LibraryMediaType::Audio => "audio",
+        }
+    }
+}
+
+/// A free-text search, optionally narrowed by media type and year.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchParams {
+    pub q: String,
+    /// Any of these media types; all of them when empty.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_media_types"
+    )]
+    pub media_type: Vec<LibraryMediaType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year_start: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year_end: Option<u16>,
+    /// 1-based; NASA returns [`PAGE_SIZE`] items per page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct SearchParamsBuilder {
+    q: String,
+    media_type: Vec<LibraryMediaType>,
+    year_start: Option<u16>,
+    year_end: Option<u16>,
+    page: Option<u32>,
+}
+
+impl SearchParamsBuilder {
+    /// Adds `media_type` to the accepted types; call it again to accept more.
+    pub fn media_type(mut self, media_type: LibraryMediaType) -> Self {
+        if !self.media_type.contains(&media_type) {
+            self.media_type.push(media_type);
+        }
+        self
+    }
+
+    pub fn year_start(mut self, year_start: u16) -> Self {
+        self.year_start = Some(year_start);
+        self
+    }
+
+    pub fn year_end(mut self, year_end: u16) -> Self {
+        self.year_end = Some(year_end);
+        self
+    }
+
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<SearchParams> {
+        if self.q.trim().is_empty() {
+            return Err(ImageLibraryError::ImageLibraryParamsError(
+                "Search query cannot be empty".to_string(),
+            )
+            .into());
+        }
+        if self.page == Some(0) {
+            return Err(ImageLibraryError::ImageLibraryParamsError(
+                "Page numbers start at 1".to_string(),
+            )
+            .into());
+        }
+        if let (Some(year_start), Some(year_end)) = (self.year_start, self.year_end)
+            && year_start > year_end
+        {
+            return Err(ImageLibraryError::ImageLibraryParamsError(
+                "Start year cannot be greater than end year".to_string(),
+            )
+            .into());
+        }
+
+        Ok(SearchParams {
+            q: self.q,
+            media_type: self.media_type,
+            year_start: self.year_start,
+            year_end: self.year_end,
+            page: self.page,
+        })
+    }
+}
+
+impl SearchParams {
+    pub fn builder(q: impl Into<String>) -> SearchParamsBuilder {
+        SearchParamsBuilder {
+            q: q.into(),
+            media_type: Vec::new(),
+            year_start: None,
+            year_end: None,
+            page: None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.page {
+            Some(page) => format!("search {:?}, page {page}", self.q),
+            None => format!("search {:?}", self.q),
+        }
+    }
+}
+
+fn serialize_media_types<S: Serializer>(
+    media_types: &[LibraryMediaType],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let media_types: Vec<_> = media_types.iter().map(LibraryMediaType::as_str).collect();
+    serializer.serialize_str(&media_types.join(","))
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchResponse {
+    pub collection: SearchCollection,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SearchCollection {
+    pub version: String,
+    /// The search that produced this page.
+    pub href: Url,
+    pub items: Vec<LibraryItem>,
+    /// Navigation links; `rel` is `"next"` or `"prev"`.
+    #[serde(default)]
+    pub links: Vec<Link>,
+    pub metadata: SearchMetadata,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMetadata {
+    /// Matches across all pages, not just this one.
+    pub total_hits: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LibraryItem {
+    /// The item's asset manifest, a JSON list of every file for it.
+    pub href: Url,
+    pub data: Vec<ItemData>,
+    /// Previews such as thumbnails and captions.
+    #[serde(default)]
+    pub links: Vec<Link>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ItemData {
+    pub nasa_id: String,
+    pub title: String,
+    pub media_type: LibraryMediaType,
+    pub date_created: DateTime<Utc>,
+    pub center: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub href: Url,
+    pub rel: String,
+    pub prompt: Option<String>,
+    pub render: Option<String>,
+}
+
+impl SearchResponse {
+    /// The asset manifest url of every item on this page, in order.
+    pub fn manifest_urls(&self) -> Vec<&Url> {
+        self.collection
+            .items
+            .iter()
+            .map(|item| &item.href)
+            .collect()
+    }
+
+    /// How many pages of `per_page` items the whole search spans, e.g. with
+    /// [`PAGE_SIZE`]. Zero when `per_page` is zero.
+    pub fn total_pages(&self, per_page: usize) -> u64 {
+        match per_page {
+            0 => 0,
+            per_page => self
+                .collection
+                .metadata
+                .total_hits
+                .div_ceil(per_page as u64),
+        }
+    }
+}
+
+impl Page for SearchResponse {
+    type Item = LibraryItem;
+    type Request = Url;
+
+    fn next_request(&self) -> Option<Url> {
+        self.collection
+            .links
+            .iter()
+            .find(|link| link.rel == "next")
+            .map(|link| link.href.clone())
+    }
+
+    fn items(self) -> Vec<LibraryItem> {
+        self.collection.items
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct ImageLibraryApiError {
+    code: u16,
+    msg: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ImageLibraryError {
+    #[error(transparent)]
+    ImageLibraryApiError(#[from] ImageLibraryApiError),
+    #[error("invalid parameters: {0}")]
+    ImageLibraryParamsError(String),
+}
+
+impl ImageLibraryError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ImageLibraryError::ImageLibraryApiError(err) => Some(err.code),
+            ImageLibraryError::ImageLibraryParamsError(_) => None,
+        }
+    }
+}
+
+impl ImageLibraryApi {
+    pub fn new(reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default Image Library base url is valid");
+        Self {
+            base_url,
+            reqwest_client,
+        }
+    }
+
+    /// Like [`ImageLibraryApi::new`], but sends requests to `base_url` instead
+    /// of NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(reqwest_client: reqwest::Client, base_url: Url) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        Ok(Self {
+            base_url,
+            reqwest_client,
+        })
+    }
+
+    /// Fetches the page a [`Link`] such as the `"next"` one points to.
+    pub async fn get_page(&self, url: Url) -> StellariaResult<SearchResponse> {
+        let context = format!("page {url}");
+        self.send(&url, None)
+            .await
+            .map_err(|err| err.with_context(ENDPOINT, context))
+    }
+
+    /// Streams the items of every page from `params.page` on, following
+    /// each page's `"next"` link once the previous page is used up.
+    ///
+    /// The stream ends after the first failed page.
+    pub fn search_all(
+        &self,
+        params: SearchParams,
+    ) -> impl Stream<Item = StellariaResult<LibraryItem>> + '_ {
+        stream::once(self.get(params)).flat_map(move |first_page| match first_page {
+            Ok(first_page) => paginate(first_page, move |next| self.get_page(next)).left_stream(),
+            Err(err) => stream::iter([Err(err)]).right_stream(),
+        })
+    }
+
+    async fn send(
+        &self,
+        url: &Url,
+        params: Option<&SearchParams>,
+    ) -> StellariaResult<SearchResponse> {
+        request::get_json(
+            &self.reqwest_client,
+            url,
+            &params,
+            None,
+            ENDPOINT,
+            |code, body| ImageLibraryApiError {
+                code,
+                msg: request::error_message(body),
+            },
+        )
+        .await
+    }
+}
+
+impl Api for ImageLibraryApi {
+    type Params = SearchParams;
+    type Response = SearchResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        self.send(&self.base_url, Some(&params))
+            .await
+            .map_err(|err| err.with_context(ENDPOINT, params.describe()))
+    }
+}
+
+impl From<ImageLibraryApiError> for StellariaError {
+    fn from(err: ImageLibraryApiError) -> Self {
+        ImageLibraryError::ImageLibraryApiError(err).into()
+    }
+}
+
+impl From<ImageLibraryError> for StellariaError {
+    fn from(err: ImageLibraryError) -> Self {
+        ApiError::ImageLibraryError(err).into()
+    }
+}
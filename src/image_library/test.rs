@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::Api;
+    use crate::image_library::{ImageLibraryApi, LibraryMediaType, PAGE_SIZE, SearchParams};
+    use crate::pagination::Page;
+    use crate::test_util::mock_base_url;
+
+    use futures::StreamExt;
+    use reqwest::Client;
+    use serde_json::json;
+    use url::Url;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn item_json(nasa_id: &str) -> serde_json::Value {
+        json!({
+            "href": format!("https://images-assets.nasa.gov/image/{nasa_id}/collection.json"),
+            "data": [{
+                "center": "JSC",
+                "title": format!("Apollo photo {nasa_id}"),
+                "nasa_id": nasa_id,
+                "date_created": "1969-07-20T00:00:00Z",
+                "keywords": ["Apollo 11", "Moon"],
+                "media_type": "image",
+                "description": "Astronaut on the lunar surface.",
+            }],
+            "links": [{
+                "href": format!("https://images-assets.nasa.gov/image/{nasa_id}/{nasa_id}~thumb.jpg"),
+                "rel": "preview",
+                "render": "image",
+            }],
+        })
+    }
+
+    fn page_json(items: &[&str], next: Option<String>) -> serde_json::Value {
+        let links: Vec<_> = next
+            .into_iter()
+            .map(|href| json!({"rel": "next", "prompt": "Next", "href": href}))
+            .collect();
+        json!({
+            "collection": {
+                "version": "1.0",
+                "href": "https://images-api.nasa.gov/search?q=apollo%2011",
+                "items": items.iter().map(|id| item_json(id)).collect::<Vec<_>>(),
+                "metadata": {"total_hits": 3},
+                "links": links,
+            }
+        })
+    }
+
+    fn mock_library(server: &MockServer) -> ImageLibraryApi {
+        ImageLibraryApi::with_base_url(Client::new(), mock_base_url(server, "/search")).unwrap()
+    }
+
+    // ==================== Query Tests ====================
+
+    #[tokio::test]
+    async fn test_search_params_serialize_to_query() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("q", "apollo 11"))
+            .and(query_param("media_type", "image,video"))
+            .and(query_param("year_start", "1969"))
+            .and(query_param("year_end", "1972"))
+            .and(query_param("page", "2"))
+            .and(query_param_is_missing("api_key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page_json(&[], None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = SearchParams::builder("apollo 11")
+            .media_type(LibraryMediaType::Image)
+            .media_type(LibraryMediaType::Video)
+            .media_type(LibraryMediaType::Image)
+            .year_start(1969)
+            .year_end(1972)
+            .page(2)
+            .build()
+            .unwrap();
+        mock_library(&server).get(params).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unset_filters_are_omitted() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("q", "nebula"))
+            .and(query_param_is_missing("media_type"))
+            .and(query_param_is_missing("year_start"))
+            .and(query_param_is_missing("page"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page_json(&[], None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = SearchParams::builder("nebula").build().unwrap();
+        mock_library(&server).get(params).await.unwrap();
+    }
+
+    // ==================== Response Tests ====================
+
+    #[test]
+    fn test_search_response_shape() {
+        let next = "https://images-api.nasa.gov/search?q=apollo%2011&page=2".to_string();
+        let response: crate::image_library::SearchResponse =
+            serde_json::from_value(page_json(&["as11-40-5874", "as11-40-5875"], Some(next)))
+                .unwrap();
+
+        assert_eq!(response.collection.metadata.total_hits, 3);
+        let item = &response.collection.items[0];
+        assert_eq!(item.data[0].nasa_id, "as11-40-5874");
+        assert_eq!(item.data[0].media_type, LibraryMediaType::Image);
+        assert_eq!(item.data[0].keywords, ["Apollo 11", "Moon"]);
+        assert_eq!(item.links[0].rel, "preview");
+
+        let manifests: Vec<_> = response
+            .manifest_urls()
+            .into_iter()
+            .map(Url::as_str)
+            .collect();
+        assert_eq!(
+            manifests,
+            [
+                "https://images-assets.nasa.gov/image/as11-40-5874/collection.json",
+                "https://images-assets.nasa.gov/image/as11-40-5875/collection.json",
+            ]
+        );
+        assert_eq!(
+            response.next_request().unwrap().as_str(),
+            "https://images-api.nasa.gov/search?q=apollo%2011&page=2"
+        );
+    }
+
+    // ==================== Pagination Tests ====================
+
+    #[tokio::test]
+    async fn test_search_all_follows_next_links() {
+        let server = MockServer::start().await;
+        let next = format!("{}/search?q=apollo&page=2", server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page_json(&["c"], None)))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .and(query_param_is_missing("page"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(page_json(&["a", "b"], Some(next))),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let params = SearchParams::builder("apollo").build().unwrap();
+        let first_page = mock_library(&server).get(params.clone()).await.unwrap();
+        assert_eq!(first_page.total_pages(2), 2);
+        assert_eq!(first_page.total_pages(PAGE_SIZE), 1);
+        assert_eq!(first_page.total_pages(0), 0);
+
+        let ids: Vec<_> = mock_library(&server)
+            .search_all(params)
+            .map(|item| item.unwrap().data[0].nasa_id.clone())
+            .collect()
+            .await;
+
+        assert_eq!(ids, ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_ends_on_failed_page() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("server error"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = SearchParams::builder("apollo").build().unwrap();
+        let items: Vec<_> = mock_library(&server).search_all(params).collect().await;
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_ref().unwrap_err().status(), Some(500));
+    }
+
+    // ==================== Params Tests ====================
+
+    #[test]
+    fn test_builder_rejects_invalid_params() {
+        assert!(SearchParams::builder(" ").build().is_err());
+        assert!(SearchParams::builder("moon").page(0).build().is_err());
+        let reversed = SearchParams::builder("moon")
+            .year_start(2000)
+            .year_end(1999)
+            .build();
+        assert!(reversed.unwrap_err().is_client_error());
+    }
+}
@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod test;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+use url::Url;
+
+use crate::keys::KeyRing;
+use crate::request;
+use crate::{Api, ApiError, ApiToken, StellariaError, StellariaResult};
+
+const BASE_URL: &str = "https://api.nasa.gov/insight_weather/";
+pub(crate) const PATH: &str = "insight_weather/";
+const ENDPOINT: &str = "InSight";
+
+#[derive(Debug)]
+pub struct InsightApi {
+    keys: KeyRing,
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+}
+
+/// The feed has no filters; NASA always returns its most recent sols.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InsightParams;
+
+impl Serialize for InsightParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [("feedtype", "json"), ("ver", "1.0")].serialize(serializer)
+    }
+}
+
+/// Weather summaries for the most recent sols (Martian days).
+///
+/// NASA stopped updating this feed at the end of the InSight mission, so it
+/// may well come back with no sols at all.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(try_from = "RawInsightResponse")]
+pub struct InsightResponse {
+    /// Each sol number with its summary, in the order of `sol_keys`.
+    pub sols: Vec<(String, SolWeather)>,
+    pub sol_keys: Vec<String>,
+    pub validity_checks: Option<ValidityChecks>,
+}
+
+/// The sol-keyed object NASA sends, before ordering it by `sol_keys`.
+#[derive(Deserialize)]
+struct RawInsightResponse {
+    #[serde(default)]
+    sol_keys: Vec<String>,
+    validity_checks: Option<ValidityChecks>,
+    #[serde(flatten)]
+    sols: HashMap<String, serde_json::Value>,
+}
+
+impl TryFrom<RawInsightResponse> for InsightResponse {
+    type Error = serde_json::Error;
+
+    fn try_from(mut raw: RawInsightResponse) -> Result<Self, Self::Error> {
+        let sols = raw
+            .sol_keys
+            .iter()
+            .filter_map(|sol| Some((sol.clone(), raw.sols.remove(sol)?)))
+            .map(|(sol, weather)| Ok((sol, serde_json::from_value(weather)?)))
+            .collect::<serde_json::Result<_>>()?;
+
+        Ok(InsightResponse {
+            sols,
+            sol_keys: raw.sol_keys,
+            validity_checks: raw.validity_checks,
+        })
+    }
+}
+
+/// One sol's summary. Sensors without enough data that sol are `None`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SolWeather {
+    /// Atmospheric temperature in °C.
+    #[serde(rename = "AT")]
+    pub temperature: Option<SensorSummary>,
+    /// Horizontal wind speed in m/s.
+    #[serde(rename = "HWS")]
+    pub wind_speed: Option<SensorSummary>,
+    /// Atmospheric pressure in Pa.
+    #[serde(rename = "PRE")]
+    pub pressure: Option<SensorSummary>,
+    #[serde(rename = "WD")]
+    pub wind_direction: Option<WindDirection>,
+    #[serde(rename = "First_UTC")]
+    pub first_utc: DateTime<Utc>,
+    #[serde(rename = "Last_UTC")]
+    pub last_utc: DateTime<Utc>,
+    #[serde(rename = "Season")]
+    pub season: Option<String>,
+    #[serde(rename = "Northern_season")]
+    pub northern_season: Option<String>,
+    #[serde(rename = "Southern_season")]
+    pub southern_season: Option<String>,
+}
+
+/// Statistics over one sol of sensor samples.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SensorSummary {
+    #[serde(rename = "av")]
+    pub average: f64,
+    #[serde(rename = "mn")]
+    pub min: f64,
+    #[serde(rename = "mx")]
+    pub max: f64,
+    /// How many samples the summary covers.
+    #[serde(rename = "ct")]
+    pub count: u64,
+}
+
+/// Wind samples per compass sector.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WindDirection {
+    pub most_common: Option<WindSector>,
+    /// Keyed by sector number, `"0"` (N) to `"15"` (NNW).
+    #[serde(flatten)]
+    pub sectors: BTreeMap<String, WindSector>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WindSector {
+    pub compass_degrees: f64,
+    pub compass_point: CompassPoint,
+    #[serde(rename = "ct")]
+    pub count: u64,
+}
+
+/// The 16 points of the compass, clockwise from north.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CompassPoint {
+    N,
+    Nne,
+    Ne,
+    Ene,
+    E,
+    Ese,
+    Se,
+    Sse,
+    S,
+    Ssw,
+    Sw,
+    Wsw,
+    W,
+    Wnw,
+    Nw,
+    Nnw,
+}
+
+impl CompassPoint {
+    pub const ALL: [CompassPoint; 16] = [
+        CompassPoint::N,
+        CompassPoint::Nne,
+        CompassPoint::Ne,
+        CompassPoint::Ene,
+        CompassPoint::E,
+        CompassPoint::Ese,
+        CompassPoint::Se,
+        CompassPoint::Sse,
+        CompassPoint::S,
+        CompassPoint::Ssw,
+        CompassPoint::Sw,
+        CompassPoint::Wsw,
+        CompassPoint::W,
+        CompassPoint::Wnw,
+        CompassPoint::Nw,
+        CompassPoint::Nnw,
+    ];
+
+    /// The bearing in degrees clockwise from north, e.g. 292.5 for `WNW`.
+    pub fn degrees(&self) -> f64 {
+        *self as u8 as f64 * 22.5
+    }
+
+    /// The nearest point to a bearing in degrees clockwise from north.
+    pub fn from_degrees(degrees: f64) -> Self {
+        let sector = (degrees.rem_euclid(360.0) / 22.5).round() as usize % 16;
+        Self::ALL[sector]
+    }
+}
+
+/// Which hours of each sol had data, per sol and sensor.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ValidityChecks {
+    pub sol_hours_required: Option<u32>,
+    #[serde(default)]
+    pub sols_checked: Vec<String>,
+    /// Keyed by sol, then by sensor (`AT`, `HWS`, `PRE`, `WD`).
+    #[serde(flatten)]
+    pub sols: BTreeMap<String, BTreeMap<String, SensorValidity>>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SensorValidity {
+    pub sol_hours_with_data: Vec<u32>,
+    pub valid: bool,
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct InsightApiError {
+    code: u16,
+    msg: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum InsightError {
+    #[error(transparent)]
+    InsightApiError(#[from] InsightApiError),
+}
+
+impl InsightError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            InsightError::InsightApiError(err) => Some(err.code),
+        }
+    }
+}
+
+impl InsightApi {
+    pub fn new(api_key: impl Into<ApiToken>, reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default InSight base url is valid");
+        Self::with_key_ring(KeyRing::new(api_key.into(), []), reqwest_client, base_url)
+    }
+
+    /// Like [`InsightApi::new`], but sends requests to `base_url` instead of
+    /// NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(
+        api_key: impl Into<ApiToken>,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        Ok(Self::with_key_ring(
+            KeyRing::new(api_key.into(), []),
+            reqwest_client,
+            base_url,
+        ))
+    }
+
+    pub(crate) fn with_key_ring(
+        keys: KeyRing,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> Self {
+        Self {
+            keys,
+            base_url,
+            reqwest_client,
+        }
+    }
+
+    /// Extra api_keys to rotate to when the active key gets a 429.
+    pub fn with_fallback_keys<T: Into<ApiToken>>(
+        mut self,
+        fallback_keys: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let fallback_keys = fallback_keys.into_iter().map(Into::into);
+        self.keys = KeyRing::new(self.keys.primary().clone(), fallback_keys);
+        self
+    }
+}
+
+impl Api for InsightApi {
+    type Params = InsightParams;
+    type Response = InsightResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        async {
+            let body = request::get_body(
+                &self.reqwest_client,
+                &self.base_url,
+                &params,
+                Some(&self.keys),
+                ENDPOINT,
+                |code, body| InsightApiError {
+                    code,
+                    msg: request::error_message(body),
+                },
+            )
+            .await?;
+            if body.iter().all(u8::is_ascii_whitespace) {
+                return Ok(InsightResponse::default());
+            }
+            request::parse_json(&body)
+        }
+        .await
+        .map_err(|err| err.with_context(ENDPOINT, "the latest sols"))
+    }
+}
+
+impl From<InsightApiError> for StellariaError {
+    fn from(err: InsightApiError) -> Self {
+        InsightError::InsightApiError(err).into()
+    }
+}
+
+impl From<InsightError> for StellariaError {
+    fn from(err: InsightError) -> Self {
+        ApiError::InsightError(err).into()
+    }
+}
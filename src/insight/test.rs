@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::Api;
+    use crate::insight::{CompassPoint, InsightApi, InsightParams, InsightResponse, WindSector};
+    use crate::test_util::{TEST_KEY, mock_base_url};
+
+    use chrono::{TimeZone, Utc};
+    use reqwest::Client;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sensor(av: f64, mn: f64, mx: f64) -> serde_json::Value {
+        json!({"av": av, "ct": 177556, "mn": mn, "mx": mx})
+    }
+
+    fn sol_json(first: &str, last: &str) -> serde_json::Value {
+        json!({
+            "AT": sensor(-62.314, -96.872, -15.908),
+            "First_UTC": first,
+            "HWS": sensor(7.233, 1.051, 22.455),
+            "Last_UTC": last,
+            "Month_ordinal": 10,
+            "Northern_season": "early winter",
+            "PRE": sensor(750.563, 722.0901, 768.791),
+            "Season": "fall",
+            "Southern_season": "early summer",
+            "WD": {
+                "1": {"compass_degrees": 22.5, "compass_point": "NNE", "compass_right": 0.38, "compass_up": 0.92, "ct": 28},
+                "most_common": {"compass_degrees": 202.5, "compass_point": "SSW", "compass_right": -0.38, "compass_up": -0.92, "ct": 85},
+            },
+        })
+    }
+
+    /// Trimmed from a response the feed served in October 2020.
+    fn sample_json() -> serde_json::Value {
+        json!({
+            "676": sol_json("2020-10-20T19:11:55Z", "2020-10-21T19:51:29Z"),
+            "675": sol_json("2020-10-19T18:32:20Z", "2020-10-20T19:11:55Z"),
+            "sol_keys": ["675", "676"],
+            "validity_checks": {
+                "675": {
+                    "AT": {"sol_hours_with_data": [0, 1, 2, 3], "valid": true},
+                    "WD": {"sol_hours_with_data": [0, 1], "valid": false},
+                },
+                "676": {
+                    "AT": {"sol_hours_with_data": [0, 1, 2], "valid": true},
+                },
+                "sol_hours_required": 18,
+                "sols_checked": ["675", "676"],
+            },
+        })
+    }
+
+    fn mock_insight(server: &MockServer) -> InsightApi {
+        InsightApi::with_base_url(
+            TEST_KEY,
+            Client::new(),
+            mock_base_url(server, "/insight_weather/"),
+        )
+        .unwrap()
+    }
+
+    // ==================== Response Tests ====================
+
+    #[test]
+    fn test_sols_follow_sol_keys_order() {
+        let response: InsightResponse = serde_json::from_value(sample_json()).unwrap();
+
+        let sols: Vec<_> = response.sols.iter().map(|(sol, _)| sol.as_str()).collect();
+        assert_eq!(sols, ["675", "676"]);
+        assert_eq!(response.sol_keys, ["675", "676"]);
+
+        let (_, weather) = &response.sols[0];
+        assert_eq!(
+            weather.first_utc,
+            Utc.with_ymd_and_hms(2020, 10, 19, 18, 32, 20).unwrap()
+        );
+        let temperature = weather.temperature.unwrap();
+        assert_eq!(temperature.average, -62.314);
+        assert_eq!(temperature.min, -96.872);
+        assert_eq!(temperature.count, 177556);
+        assert_eq!(weather.pressure.unwrap().max, 768.791);
+        assert_eq!(weather.season.as_deref(), Some("fall"));
+        let wind = weather.wind_direction.as_ref().unwrap();
+        assert_eq!(
+            wind.most_common.as_ref().unwrap().compass_point,
+            CompassPoint::Ssw
+        );
+        assert_eq!(wind.sectors["1"].count, 28);
+    }
+
+    #[test]
+    fn test_compass_point_and_degrees() {
+        let sector: WindSector = serde_json::from_value(json!({
+            "compass_degrees": 292.5,
+            "compass_point": "WNW",
+            "compass_right": -0.92,
+            "compass_up": 0.38,
+            "ct": 12,
+        }))
+        .unwrap();
+
+        assert_eq!(sector.compass_point, CompassPoint::Wnw);
+        assert_eq!(sector.compass_point.degrees(), sector.compass_degrees);
+        assert_eq!(CompassPoint::from_degrees(292.5), CompassPoint::Wnw);
+        assert_eq!(CompassPoint::from_degrees(355.0), CompassPoint::N);
+        assert_eq!(CompassPoint::from_degrees(-22.5), CompassPoint::Nnw);
+    }
+
+    #[test]
+    fn test_validity_checks_per_sol_and_sensor() {
+        let response: InsightResponse = serde_json::from_value(sample_json()).unwrap();
+
+        let checks = response.validity_checks.unwrap();
+        assert_eq!(checks.sol_hours_required, Some(18));
+        assert_eq!(checks.sols_checked, ["675", "676"]);
+        assert!(checks.sols["675"]["AT"].valid);
+        assert!(!checks.sols["675"]["WD"].valid);
+        assert_eq!(checks.sols["676"]["AT"].sol_hours_with_data, [0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_retired_feed_returns_no_sols() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/insight_weather/"))
+            .and(query_param("feedtype", "json"))
+            .and(query_param("ver", "1.0"))
+            .and(query_param("api_key", "TEST_KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sol_keys": [],
+                "validity_checks": {"sol_hours_required": 18, "sols_checked": []},
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = mock_insight(&server).get(InsightParams).await.unwrap();
+
+        assert!(response.sols.is_empty());
+        assert!(response.sol_keys.is_empty());
+        assert!(response.validity_checks.unwrap().sols.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_empty_body_means_no_sols() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/insight_weather/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(""))
+            .mount(&server)
+            .await;
+
+        let response = mock_insight(&server).get(InsightParams).await.unwrap();
+
+        assert!(response.sols.is_empty());
+        assert_eq!(response.validity_checks, None);
+    }
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::cmp::Reverse;
+use std::sync::{Arc, Mutex};
+
+use crate::{ApiToken, RateLimit};
+
+/// The primary api_key plus optional fallbacks, with per-key remaining quota
+/// shared by every endpoint built from the same client.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyRing {
+    keys: Arc<[ApiToken]>,
+    state: Arc<Mutex<KeyState>>,
+}
+
+#[derive(Debug)]
+struct KeyState {
+    active: usize,
+    remaining: Vec<Option<u32>>,
+}
+
+impl KeyRing {
+    pub(crate) fn new(primary: ApiToken, fallbacks: impl IntoIterator<Item = ApiToken>) -> Self {
+        let keys: Arc<[ApiToken]> = std::iter::once(primary).chain(fallbacks).collect();
+        let state = KeyState {
+            active: 0,
+            remaining: vec![None; keys.len()],
+        };
+
+        Self {
+            keys,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub(crate) fn primary(&self) -> &ApiToken {
+        &self.keys[0]
+    }
+
+    pub(crate) fn get(&self, index: usize) -> &ApiToken {
+        &self.keys[index]
+    }
+
+    #[cfg_attr(not(feature = "apod"), allow(dead_code))]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &ApiToken> {
+        self.keys.iter()
+    }
+
+    pub(crate) fn active(&self) -> usize {
+        self.lock().active
+    }
+
+    pub(crate) fn record(&self, index: usize, rate_limit: RateLimit) {
+        if let Some(remaining) = rate_limit.remaining {
+            self.lock().remaining[index] = Some(remaining);
+        }
+    }
+
+    /// Marks `index` as out of quota and switches to the untried key with the
+    /// most remaining quota (unknown counts as fresh, ties go to the earlier
+    /// key), if any.
+    pub(crate) fn rotate(&self, index: usize, tried: &[bool]) -> Option<usize> {
+        let mut state = self.lock();
+        state.remaining[index] = Some(0);
+
+        let next = (0..self.keys.len())
+            .filter(|candidate| !tried[*candidate])
+            .min_by_key(|candidate| Reverse(state.remaining[*candidate].unwrap_or(u32::MAX)))?;
+        state.active = next;
+        Some(next)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, KeyState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_picks_freshest_untried_key() {
+        let keys = KeyRing::new("A".into(), ["B".into(), "C".into()]);
+        let limit = |remaining| RateLimit {
+            limit: Some(1000),
+            remaining: Some(remaining),
+        };
+        keys.record(1, limit(10));
+        keys.record(2, limit(500));
+
+        assert_eq!(keys.rotate(0, &[true, false, false]), Some(2));
+        assert_eq!(keys.active(), 2);
+        assert_eq!(keys.rotate(2, &[true, false, true]), Some(1));
+        assert_eq!(keys.rotate(1, &[true, true, true]), None);
+    }
+
+    #[test]
+    fn test_key_ring_debug_omits_api_keys() {
+        let keys = KeyRing::new("SECRET_KEY".into(), ["OTHER_KEY".into()]);
+
+        let debug = format!("{keys:?}");
+        assert!(
+            !debug.contains("SECRET_KEY") && !debug.contains("OTHER_KEY"),
+            "{debug}"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! [`StellariaClient::from_layered`], behind the `config` feature so only
+//! callers who want a config file compile the TOML parser.
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer};
+use url::Url;
+
+use crate::{StellariaClient, StellariaError, StellariaResult};
+
+const CONFIG_FILE: &str = "stellaria.toml";
+const ENV_PREFIX: &str = "STELLARIA_";
+
+/// Settings read from `stellaria.toml`, each overridable by a `STELLARIA_` env var.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayeredConfig {
+    key: Option<String>,
+    base_url: Option<Url>,
+    /// Seconds, or a duration such as `"30s"` or `"1m 30s"`.
+    #[cfg_attr(not(feature = "apod"), allow(dead_code))]
+    #[serde(default, deserialize_with = "deserialize_timeout")]
+    timeout: Option<Duration>,
+}
+
+fn deserialize_timeout<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawTimeout {
+        Seconds(u64),
+        Text(String),
+    }
+
+    let timeout = match RawTimeout::deserialize(deserializer)? {
+        RawTimeout::Seconds(secs) => parse_timeout(&secs.to_string()),
+        RawTimeout::Text(text) => parse_timeout(&text),
+    };
+    timeout.map(Some).map_err(serde::de::Error::custom)
+}
+
+/// Parses a positive duration: bare seconds (`30`) or numbers with units
+/// `ms`, `s`, `m` and `h`, optionally combined (`1m 30s`).
+fn parse_timeout(input: &str) -> Result<Duration, String> {
+    let invalid =
+        || format!("invalid timeout {input:?}, expected e.g. \"30\", \"30s\" or \"1m 30s\"");
+    let input = input.trim();
+    if let Ok(secs) = input.parse::<u64>() {
+        return (secs > 0)
+            .then(|| Duration::from_secs(secs))
+            .ok_or_else(invalid);
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = input;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+        rest = rest[digits..].trim_start();
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let secs_per_unit = match &rest[..unit_len] {
+            "ms" => None,
+            "s" | "sec" | "secs" => Some(1),
+            "m" | "min" | "mins" => Some(60),
+            "h" | "hr" | "hrs" => Some(3600),
+            _ => return Err(invalid()),
+        };
+        let part = match secs_per_unit {
+            Some(secs_per_unit) => {
+                Duration::from_secs(value.checked_mul(secs_per_unit).ok_or_else(invalid)?)
+            }
+            None => Duration::from_millis(value),
+        };
+        total = total.checked_add(part).ok_or_else(invalid)?;
+        rest = rest[unit_len..].trim_start();
+    }
+    if total.is_zero() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+impl StellariaClient {
+    /// Builds a client from defaults, then `./stellaria.toml` (if present),
+    /// then `STELLARIA_KEY` / `STELLARIA_BASE_URL` / `STELLARIA_TIMEOUT` from
+    /// the environment. The timeout applies to APOD requests, like
+    /// [`StellariaClientBuilder::timeout`](crate::StellariaClientBuilder::timeout).
+    pub fn from_layered() -> StellariaResult<Self> {
+        Self::from_layered_at(Path::new(CONFIG_FILE), |name| std::env::var(name).ok())
+    }
+
+    pub(crate) fn from_layered_at(
+        config_file: &Path,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> StellariaResult<Self> {
+        let mut config = match std::fs::read_to_string(config_file) {
+            Ok(contents) => toml::from_str(&contents).map_err(|err| {
+                StellariaError::ConfigError(format!("{}: {err}", config_file.display()))
+            })?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => LayeredConfig::default(),
+            Err(err) => {
+                return Err(StellariaError::ConfigError(format!(
+                    "{}: {err}",
+                    config_file.display()
+                )));
+            }
+        };
+
+        let env_var = |name: &str| env(&format!("{ENV_PREFIX}{name}"));
+        if let Some(key) = env_var("KEY") {
+            config.key = Some(key);
+        }
+        if let Some(base_url) = env_var("BASE_URL") {
+            config.base_url = Some(Url::parse(&base_url)?);
+        }
+        if let Some(timeout) = env_var("TIMEOUT") {
+            config.timeout = Some(parse_timeout(&timeout).map_err(|err| {
+                StellariaError::ConfigError(format!("{ENV_PREFIX}TIMEOUT: {err}"))
+            })?);
+        }
+
+        let key = config.key.ok_or_else(|| {
+            StellariaError::ConfigError(format!("no api key in {CONFIG_FILE} or {ENV_PREFIX}KEY"))
+        })?;
+        let mut builder = StellariaClient::builder(key);
+        if let Some(base_url) = config.base_url {
+            builder = builder.gateway_base(base_url);
+        }
+        #[cfg(feature = "apod")]
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_config(name: &str, contents: &str) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("stellaria-{}-{name}.toml", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_env_overrides_config_file() {
+        let path = write_config(
+            "override",
+            "key = \"FILE_KEY\"\nbase_url = \"https://mirror.example.com/nasa/\"\ntimeout = \"10s\"\n",
+        );
+
+        let client = StellariaClient::from_layered_at(&path, |name| match name {
+            "STELLARIA_KEY" => Some("ENV_KEY".to_string()),
+            "STELLARIA_TIMEOUT" => Some("1m 30s".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        #[cfg(feature = "apod")]
+        let file_only = StellariaClient::from_layered_at(&path, |_| None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(client.api_token.expose(), "ENV_KEY");
+        #[cfg(feature = "apod")]
+        {
+            let apod = format!("{:?}", client.apod);
+            assert!(apod.contains("mirror.example.com"));
+            assert!(apod.contains("timeout: Some(90s)"));
+            assert!(format!("{:?}", file_only.apod).contains("timeout: Some(10s)"));
+        }
+    }
+
+    #[test]
+    fn test_timeout_formats() {
+        assert_eq!(parse_timeout("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_timeout("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_timeout("1500ms"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_timeout("1h 30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_timeout(" 1m30s "), Ok(Duration::from_secs(90)));
+        for invalid in ["", "0", "0s", "s", "30 parsecs", "-5", "1.5s"] {
+            assert!(parse_timeout(invalid).is_err(), "{invalid:?} parsed");
+        }
+    }
+
+    #[test]
+    fn test_timeout_in_seconds_from_config_file() {
+        let path = write_config("timeout", "key = \"FILE_KEY\"\ntimeout = 45\n");
+        let client = StellariaClient::from_layered_at(&path, |_| None);
+        std::fs::remove_file(&path).unwrap();
+        #[cfg(feature = "apod")]
+        assert!(format!("{:?}", client.unwrap().apod).contains("timeout: Some(45s)"));
+        #[cfg(not(feature = "apod"))]
+        assert!(client.is_ok());
+
+        let result = StellariaClient::from_layered_at(
+            Path::new("stellaria-missing.toml"),
+            |name| match name {
+                "STELLARIA_KEY" => Some("ENV_KEY".to_string()),
+                "STELLARIA_TIMEOUT" => Some("soon".to_string()),
+                _ => None,
+            },
+        );
+        let Err(StellariaError::ConfigError(msg)) = result else {
+            panic!("expected a config error");
+        };
+        assert!(msg.contains("STELLARIA_TIMEOUT"));
+    }
+
+    #[test]
+    fn test_config_file_without_env() {
+        let path = write_config("file-only", "key = \"FILE_KEY\"\n");
+
+        let client = StellariaClient::from_layered_at(&path, |_| None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(client.api_token.expose(), "FILE_KEY");
+    }
+
+    #[test]
+    fn test_missing_key_fails() {
+        let path = std::env::temp_dir().join("stellaria-does-not-exist.toml");
+
+        let result = StellariaClient::from_layered_at(&path, |_| None);
+        assert!(matches!(result, Err(StellariaError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_malformed_config_file_fails() {
+        let path = write_config("malformed", "key = \n");
+
+        let result = StellariaClient::from_layered_at(&path, |_| None);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(StellariaError::ConfigError(_))));
+    }
+}
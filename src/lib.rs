@@ -1,8 +1,97 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use thiserror::Error;
+use url::Url;
 
+pub use clock::Clock;
+
+#[cfg(feature = "apod")]
 pub mod apod;
+mod clock;
+mod config;
+#[cfg_attr(not(feature = "apod"), allow(dead_code))]
+mod date_serde;
+pub mod designation;
+#[cfg(feature = "donki")]
+pub mod donki;
+#[cfg(feature = "eonet")]
+pub mod eonet;
+#[cfg(feature = "epic")]
+pub mod epic;
+#[cfg(feature = "exoplanet")]
+pub mod exoplanet;
+pub mod geo;
+#[cfg(feature = "image_library")]
+pub mod image_library;
+#[cfg(feature = "insight")]
+pub mod insight;
+#[cfg_attr(
+    not(any(
+        feature = "apod",
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "donki",
+        feature = "insight"
+    )),
+    allow(dead_code)
+)]
+mod keys;
+#[cfg(feature = "config")]
+mod layered;
+#[cfg(feature = "mars_photos")]
+pub mod mars_photos;
+#[cfg(feature = "neows")]
+pub mod neows;
+pub mod pagination;
+pub mod prelude;
+#[cfg_attr(
+    not(any(
+        feature = "apod",
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "donki",
+        feature = "eonet",
+        feature = "exoplanet",
+        feature = "insight",
+        feature = "image_library"
+    )),
+    allow(dead_code)
+)]
+mod request;
+pub mod serde_util;
+#[cfg_attr(
+    not(any(
+        feature = "apod",
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "donki",
+        feature = "eonet",
+        feature = "exoplanet",
+        feature = "insight",
+        feature = "image_library"
+    )),
+    allow(dead_code)
+)]
+mod telemetry;
+#[cfg(test)]
+#[cfg_attr(
+    not(any(
+        feature = "apod",
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "donki",
+        feature = "eonet",
+        feature = "exoplanet",
+        feature = "insight",
+        feature = "image_library"
+    )),
+    allow(dead_code)
+)]
+mod test_util;
 
 pub trait Api: Send + Sync {
     type Params;
@@ -14,9 +103,115 @@ pub trait Api: Send + Sync {
     ) -> impl std::future::Future<Output = StellariaResult<Self::Response>> + Send;
 }
 
+const GATEWAY_BASE: &str = "https://api.nasa.gov/";
+const DEFAULT_USER_AGENT: &str = concat!("stellaria/", env!("CARGO_PKG_VERSION"));
+
+/// A NASA api_key that never prints its value through `Debug` or `Display`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiToken(String);
+
+impl ApiToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Returns the raw token, e.g. for building a request by hand.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***REDACTED***")
+    }
+}
+
+impl std::fmt::Display for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***REDACTED***")
+    }
+}
+
+impl From<String> for ApiToken {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+impl From<&str> for ApiToken {
+    fn from(token: &str) -> Self {
+        Self(token.to_string())
+    }
+}
+
+impl From<&String> for ApiToken {
+    fn from(token: &String) -> Self {
+        Self(token.clone())
+    }
+}
+
+/// Quota information NASA reports in its `X-RateLimit-*` headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: Option<u32>,
+    pub remaining: Option<u32>,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let parse = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+        };
+
+        Self {
+            limit: parse("x-ratelimit-limit"),
+            remaining: parse("x-ratelimit-remaining"),
+        }
+    }
+}
+
+/// How long a `Retry-After` value asks to wait from `now`. The value is either
+/// delay-seconds (`"120"`) or an HTTP-date, which counts as no wait once past.
+#[cfg_attr(not(feature = "apod"), allow(dead_code))]
+pub(crate) fn parse_retry_after(
+    value: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<std::time::Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(std::time::Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.to_utc() - now).to_std().unwrap_or_default())
+}
+
+#[derive(Debug)]
 pub struct StellariaClient {
+    #[cfg(feature = "apod")]
     pub apod: apod::ApodApi,
-    pub api_token: String,
+    #[cfg(feature = "neows")]
+    pub neows: neows::NeoWsApi,
+    #[cfg(feature = "epic")]
+    pub epic: epic::EpicApi,
+    #[cfg(feature = "mars_photos")]
+    pub mars_photos: mars_photos::MarsPhotosApi,
+    #[cfg(feature = "donki")]
+    pub donki: donki::DonkiApi,
+    #[cfg(feature = "eonet")]
+    pub eonet: eonet::EonetApi,
+    #[cfg(feature = "exoplanet")]
+    pub exoplanet: exoplanet::ExoplanetApi,
+    #[cfg(feature = "insight")]
+    pub insight: insight::InsightApi,
+    #[cfg(feature = "image_library")]
+    pub image_library: image_library::ImageLibraryApi,
+    pub api_token: ApiToken,
+    /// The `User-Agent` header sent with every request.
+    pub user_agent: String,
 }
 
 #[derive(Error, Debug)]
@@ -28,26 +223,576 @@ pub enum StellariaError {
     ApiError(#[from] ApiError),
     #[error("error in parsing json: {0}")]
     JsonError(#[from] serde_json::Error),
+    #[error("invalid url: {0}")]
+    UrlError(#[from] url::ParseError),
+    #[error("response body ended unexpectedly after {bytes_read} bytes")]
+    TruncatedResponse { bytes_read: usize },
+    #[error("invalid configuration: {0}")]
+    ConfigError(String),
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The whole operation, retries included, outlasted its
+    /// `operation_timeout`.
+    #[error("operation timed out after {0:?}")]
+    Timeout(std::time::Duration),
+    #[error("while fetching {endpoint} for {context}: {source}")]
+    WithContext {
+        endpoint: &'static str,
+        context: String,
+        source: Box<StellariaError>,
+    },
 }
 
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum ApiError {
+    #[cfg(feature = "apod")]
     #[error(transparent)]
     ApodError(#[from] apod::ApodError),
+    #[cfg(feature = "neows")]
+    #[error(transparent)]
+    NeoWsError(#[from] neows::NeoWsError),
+    #[cfg(feature = "epic")]
+    #[error(transparent)]
+    EpicError(#[from] epic::EpicError),
+    #[cfg(feature = "mars_photos")]
+    #[error(transparent)]
+    MarsPhotosError(#[from] mars_photos::MarsPhotosError),
+    #[cfg(feature = "donki")]
+    #[error(transparent)]
+    DonkiError(#[from] donki::DonkiError),
+    #[cfg(feature = "eonet")]
+    #[error(transparent)]
+    EonetError(#[from] eonet::EonetError),
+    #[cfg(feature = "exoplanet")]
+    #[error(transparent)]
+    ExoplanetError(#[from] exoplanet::ExoplanetError),
+    #[cfg(feature = "insight")]
+    #[error(transparent)]
+    InsightError(#[from] insight::InsightError),
+    #[cfg(feature = "image_library")]
+    #[error(transparent)]
+    ImageLibraryError(#[from] image_library::ImageLibraryError),
 }
 
 pub type StellariaResult<T> = std::result::Result<T, StellariaError>;
 
+impl StellariaError {
+    /// HTTP status code behind the error, when one is known.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            StellariaError::RequestError(err) => err.status().map(|status| status.as_u16()),
+            StellariaError::ApiError(err) => err.status(),
+            StellariaError::JsonError(_)
+            | StellariaError::UrlError(_)
+            | StellariaError::TruncatedResponse { .. }
+            | StellariaError::ConfigError(_)
+            | StellariaError::IoError(_)
+            | StellariaError::Timeout(_) => None,
+            StellariaError::WithContext { source, .. } => source.status(),
+        }
+    }
+
+    /// The error underneath any [`StellariaError::WithContext`] layers.
+    pub fn root(&self) -> &StellariaError {
+        match self {
+            StellariaError::WithContext { source, .. } => source.root(),
+            err => err,
+        }
+    }
+
+    /// Like [`StellariaError::root`], but by value.
+    pub fn into_root(self) -> StellariaError {
+        match self {
+            StellariaError::WithContext { source, .. } => source.into_root(),
+            err => err,
+        }
+    }
+
+    #[cfg_attr(
+        not(any(
+            feature = "apod",
+            feature = "neows",
+            feature = "epic",
+            feature = "mars_photos",
+            feature = "donki",
+            feature = "eonet",
+            feature = "exoplanet",
+            feature = "insight",
+            feature = "image_library"
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn with_context(self, endpoint: &'static str, context: impl Into<String>) -> Self {
+        StellariaError::WithContext {
+            endpoint,
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// Whether retrying the same request later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self.root() {
+            StellariaError::RequestError(err) if err.is_timeout() || err.is_connect() => {
+                return true;
+            }
+            StellariaError::TruncatedResponse { .. } => return true,
+            _ => {}
+        }
+        matches!(self.status(), Some(429 | 500..=599))
+    }
+
+    /// Whether the request itself was at fault (bad parameters, bad key, ...).
+    pub fn is_client_error(&self) -> bool {
+        match self.root() {
+            StellariaError::ApiError(err) if err.is_invalid_params() => true,
+            _ => matches!(self.status(), Some(400..=499)),
+        }
+    }
+
+    /// Whether the request or the whole operation gave up after exceeding
+    /// its configured timeout.
+    pub fn is_timeout(&self) -> bool {
+        match self.root() {
+            StellariaError::RequestError(err) => err.is_timeout(),
+            StellariaError::Timeout(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the API rejected the request because the key ran out of quota.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(429)
+    }
+
+    /// How long the API asked to wait before retrying, from the `Retry-After`
+    /// header of a 429 or 503. Only APOD errors keep the header; for other
+    /// endpoints this is always `None`.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self.root() {
+            #[cfg(feature = "apod")]
+            StellariaError::ApiError(ApiError::ApodError(apod::ApodError::ApodApiError(err))) => {
+                err.retry_after()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ApiError {
+    pub fn status(&self) -> Option<u16> {
+        match *self {
+            #[cfg(feature = "apod")]
+            ApiError::ApodError(ref err) => err.status(),
+            #[cfg(feature = "neows")]
+            ApiError::NeoWsError(ref err) => err.status(),
+            #[cfg(feature = "epic")]
+            ApiError::EpicError(ref err) => err.status(),
+            #[cfg(feature = "mars_photos")]
+            ApiError::MarsPhotosError(ref err) => err.status(),
+            #[cfg(feature = "donki")]
+            ApiError::DonkiError(ref err) => err.status(),
+            #[cfg(feature = "eonet")]
+            ApiError::EonetError(ref err) => err.status(),
+            #[cfg(feature = "exoplanet")]
+            ApiError::ExoplanetError(ref err) => err.status(),
+            #[cfg(feature = "insight")]
+            ApiError::InsightError(ref err) => err.status(),
+            #[cfg(feature = "image_library")]
+            ApiError::ImageLibraryError(ref err) => err.status(),
+        }
+    }
+
+    fn is_invalid_params(&self) -> bool {
+        match *self {
+            #[cfg(feature = "apod")]
+            ApiError::ApodError(ref err) => matches!(err, apod::ApodError::ApodParamsError(_)),
+            #[cfg(feature = "neows")]
+            ApiError::NeoWsError(ref err) => {
+                matches!(err, neows::NeoWsError::NeoWsParamsError(_))
+            }
+            #[cfg(feature = "epic")]
+            ApiError::EpicError(ref err) => matches!(
+                err,
+                epic::EpicError::EpicParamsError(_) | epic::EpicError::FutureDate { .. }
+            ),
+            #[cfg(feature = "mars_photos")]
+            ApiError::MarsPhotosError(ref err) => {
+                matches!(
+                    err,
+                    mars_photos::MarsPhotosError::MarsPhotosParamsError(_)
+                        | mars_photos::MarsPhotosError::UnknownRover(_)
+                        | mars_photos::MarsPhotosError::UnknownCamera(_)
+                )
+            }
+            #[cfg(feature = "donki")]
+            ApiError::DonkiError(ref err) => matches!(err, donki::DonkiError::DonkiParamsError(_)),
+            #[cfg(feature = "eonet")]
+            ApiError::EonetError(ref err) => matches!(err, eonet::EonetError::EonetParamsError(_)),
+            #[cfg(feature = "exoplanet")]
+            ApiError::ExoplanetError(ref err) => {
+                matches!(err, exoplanet::ExoplanetError::ExoplanetParamsError(_))
+            }
+            #[cfg(feature = "insight")]
+            ApiError::InsightError(_) => false,
+            #[cfg(feature = "image_library")]
+            ApiError::ImageLibraryError(ref err) => matches!(
+                err,
+                image_library::ImageLibraryError::ImageLibraryParamsError(_)
+            ),
+        }
+    }
+}
+
 impl StellariaClient {
-    pub fn new(api_token: impl Into<String>) -> Self {
-        let api_token = api_token.into();
-        let reqwest_client = reqwest::Client::new();
+    pub fn new(api_token: impl Into<ApiToken>) -> Self {
+        Self::builder(api_token)
+            .build()
+            .expect("default client configuration is valid")
+    }
 
-        Self {
-            api_token: api_token.clone(),
-            apod: apod::ApodApi::new(api_token, reqwest_client.clone()),
+    /// Like [`StellariaClient::new`], but identifies itself with `user_agent`
+    /// instead of `stellaria/<version>`.
+    ///
+    /// Fails when `user_agent` isn't a valid header value, e.g. when it
+    /// contains a newline.
+    pub fn with_user_agent(
+        api_token: impl Into<ApiToken>,
+        user_agent: impl Into<String>,
+    ) -> StellariaResult<Self> {
+        Self::builder(api_token).user_agent(user_agent).build()
+    }
+
+    /// Like [`StellariaClient::new`], but APOD requests taking longer than
+    /// `timeout` fail, see [`apod::ApodApi::with_timeout`].
+    #[cfg(feature = "apod")]
+    pub fn with_timeout(api_token: impl Into<ApiToken>, timeout: std::time::Duration) -> Self {
+        Self::builder(api_token)
+            .timeout(timeout)
+            .build()
+            .expect("default client configuration is valid")
+    }
+
+    /// Like [`StellariaClient::new`], but APOD also gets a
+    /// `reqwest::blocking::Client` for [`apod::ApodApi::blocking_get`].
+    ///
+    /// Call it outside of any async runtime: the blocking client panics when
+    /// created or dropped inside one.
+    #[cfg(feature = "blocking")]
+    pub fn blocking(api_token: impl Into<ApiToken>) -> Self {
+        Self::builder(api_token)
+            .blocking()
+            .build()
+            .expect("default client configuration is valid")
+    }
+
+    /// Makes one cheap APOD request to discover the key's actual quota.
+    #[cfg(feature = "apod")]
+    pub async fn probe_rate_limit(&self) -> StellariaResult<RateLimit> {
+        let params = apod::ApodParams::builder().count(1).build()?;
+        let (_, headers) = self.apod.get_with_headers(params).await?;
+        Ok(RateLimit::from_headers(&headers))
+    }
+
+    pub fn builder(api_token: impl Into<ApiToken>) -> StellariaClientBuilder {
+        StellariaClientBuilder {
+            api_token: api_token.into(),
+            gateway_base: None,
+            fallback_api_keys: Vec::new(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            clock: Clock::system(),
+            #[cfg(feature = "apod")]
+            image_preference: apod::ImagePref::default(),
+            #[cfg(feature = "apod")]
+            hd_host_allowlist: None,
+            #[cfg(feature = "apod")]
+            check_date_window: false,
+            #[cfg(feature = "apod")]
+            validate_responses: false,
+            #[cfg(feature = "apod")]
+            skip_malformed: false,
+            #[cfg(feature = "apod")]
+            retry_policy: apod::RetryPolicy::default(),
+            #[cfg(feature = "apod")]
+            timeout: None,
+            #[cfg(feature = "apod")]
+            operation_timeout: None,
+            #[cfg(feature = "apod")]
+            chunk_days: None,
+            #[cfg(feature = "blocking")]
+            blocking: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StellariaClientBuilder {
+    api_token: ApiToken,
+    gateway_base: Option<Url>,
+    fallback_api_keys: Vec<ApiToken>,
+    user_agent: String,
+    clock: Clock,
+    #[cfg(feature = "apod")]
+    image_preference: apod::ImagePref,
+    #[cfg(feature = "apod")]
+    hd_host_allowlist: Option<Vec<String>>,
+    #[cfg(feature = "apod")]
+    check_date_window: bool,
+    #[cfg(feature = "apod")]
+    validate_responses: bool,
+    #[cfg(feature = "apod")]
+    skip_malformed: bool,
+    #[cfg(feature = "apod")]
+    retry_policy: apod::RetryPolicy,
+    #[cfg(feature = "apod")]
+    timeout: Option<std::time::Duration>,
+    #[cfg(feature = "apod")]
+    operation_timeout: Option<std::time::Duration>,
+    #[cfg(feature = "apod")]
+    chunk_days: Option<u64>,
+    #[cfg(feature = "blocking")]
+    blocking: bool,
+}
+
+impl StellariaClientBuilder {
+    /// Routes every keyed endpoint through a mirror of `https://api.nasa.gov/`,
+    /// keeping each endpoint's path below `gateway_base`.
+    pub fn gateway_base(mut self, gateway_base: Url) -> Self {
+        self.gateway_base = Some(gateway_base);
+        self
+    }
+
+    /// Extra api_keys to rotate to when the active key is rate limited.
+    pub fn fallback_api_keys<T: Into<ApiToken>>(
+        mut self,
+        fallback_api_keys: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.fallback_api_keys = fallback_api_keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the `User-Agent` header; defaults to `stellaria/<version>`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Overrides the source of "now" used by every endpoint's date defaults.
+    pub fn now_provider(
+        mut self,
+        now: impl Fn() -> chrono::DateTime<chrono::Utc> + Send + Sync + 'static,
+    ) -> Self {
+        self.clock = Clock::from_fn(now);
+        self
+    }
+
+    /// Selects which APOD image url the download helpers use.
+    #[cfg(feature = "apod")]
+    pub fn image_preference(mut self, image_preference: apod::ImagePref) -> Self {
+        self.image_preference = image_preference;
+        self
+    }
+
+    /// Falls back to the standard APOD url when `hdurl` is on another host.
+    #[cfg(feature = "apod")]
+    pub fn hd_host_allowlist<T: Into<String>>(
+        mut self,
+        hosts: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.hd_host_allowlist = Some(hosts.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Rejects APOD range responses containing dates outside the request.
+    #[cfg(feature = "apod")]
+    pub fn check_date_window(mut self, check_date_window: bool) -> Self {
+        self.check_date_window = check_date_window;
+        self
+    }
+
+    /// Rejects APOD responses with a blank title, unusable url or impossible date.
+    #[cfg(feature = "apod")]
+    pub fn validate_responses(mut self, validate_responses: bool) -> Self {
+        self.validate_responses = validate_responses;
+        self
+    }
+
+    /// Leaves malformed entries out of APOD range and count responses instead
+    /// of failing them, see [`apod::ApodApi::with_malformed_entries_skipped`].
+    #[cfg(feature = "apod")]
+    pub fn skip_malformed_entries(mut self, skip_malformed: bool) -> Self {
+        self.skip_malformed = skip_malformed;
+        self
+    }
+
+    /// Retries transient APOD failures with exponential backoff.
+    #[cfg(feature = "apod")]
+    pub fn retry_policy(mut self, retry_policy: apod::RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Fails APOD requests that take longer than `timeout`.
+    #[cfg(feature = "apod")]
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Bounds each APOD call including its retries, see
+    /// [`apod::ApodApi::with_operation_timeout`].
+    #[cfg(feature = "apod")]
+    pub fn operation_timeout(mut self, operation_timeout: std::time::Duration) -> Self {
+        self.operation_timeout = Some(operation_timeout);
+        self
+    }
+
+    /// Days per request for APOD's range helpers, at most
+    /// [`apod::MAX_CHUNK_DAYS`]; [`build`](Self::build) rejects other values.
+    #[cfg(feature = "apod")]
+    pub fn chunk_days(mut self, chunk_days: u64) -> Self {
+        self.chunk_days = Some(chunk_days);
+        self
+    }
+
+    /// Gives APOD a blocking client too, see [`StellariaClient::blocking`].
+    #[cfg(feature = "blocking")]
+    pub fn blocking(mut self) -> Self {
+        self.blocking = true;
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<StellariaClient> {
+        let mut gateway_base = match self.gateway_base {
+            Some(gateway_base) => gateway_base,
+            None => Url::parse(GATEWAY_BASE)?,
+        };
+        if gateway_base.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
         }
+        if !gateway_base.path().ends_with('/') {
+            let path = format!("{}/", gateway_base.path());
+            gateway_base.set_path(&path);
+        }
+
+        #[cfg_attr(
+            not(any(
+                feature = "apod",
+                feature = "neows",
+                feature = "epic",
+                feature = "mars_photos",
+                feature = "donki",
+                feature = "eonet",
+                feature = "exoplanet",
+                feature = "insight",
+                feature = "image_library"
+            )),
+            allow(unused_variables)
+        )]
+        let reqwest_client = reqwest::Client::builder()
+            .user_agent(&self.user_agent)
+            .build()?;
+        #[cfg_attr(
+            not(any(
+                feature = "apod",
+                feature = "neows",
+                feature = "epic",
+                feature = "mars_photos",
+                feature = "donki",
+                feature = "insight"
+            )),
+            allow(unused_variables)
+        )]
+        let keys = keys::KeyRing::new(self.api_token.clone(), self.fallback_api_keys);
+
+        #[cfg(feature = "apod")]
+        let apod = {
+            let apod = apod::ApodApi::with_key_ring(
+                keys.clone(),
+                reqwest_client.clone(),
+                gateway_base.join(apod::PATH)?,
+            )
+            .with_image_preference(self.image_preference)
+            .with_clock(self.clock.clone())
+            .with_date_window_check(self.check_date_window)
+            .with_response_validation(self.validate_responses)
+            .with_malformed_entries_skipped(self.skip_malformed)
+            .with_retry_policy(self.retry_policy);
+            let apod = match self.hd_host_allowlist {
+                Some(hosts) => apod.with_hd_host_allowlist(hosts),
+                None => apod,
+            };
+            let apod = match self.timeout {
+                Some(timeout) => apod.with_timeout(timeout),
+                None => apod,
+            };
+            let apod = match self.operation_timeout {
+                Some(operation_timeout) => apod.with_operation_timeout(operation_timeout),
+                None => apod,
+            };
+            let apod = match self.chunk_days {
+                Some(chunk_days) => apod.with_chunk_days(chunk_days)?,
+                None => apod,
+            };
+            #[cfg(feature = "blocking")]
+            let apod = if self.blocking {
+                apod.with_blocking_client(
+                    reqwest::blocking::Client::builder()
+                        .user_agent(&self.user_agent)
+                        .build()?,
+                )
+            } else {
+                apod
+            };
+            apod
+        };
+
+        Ok(StellariaClient {
+            #[cfg(feature = "apod")]
+            apod,
+            #[cfg(feature = "neows")]
+            neows: neows::NeoWsApi::with_key_ring(
+                keys.clone(),
+                reqwest_client.clone(),
+                gateway_base.join(neows::PATH)?,
+            )
+            .with_clock(self.clock.clone()),
+            #[cfg(feature = "epic")]
+            epic: epic::EpicApi::with_key_ring(
+                keys.clone(),
+                reqwest_client.clone(),
+                gateway_base.join(epic::PATH)?,
+            )
+            .with_clock(self.clock.clone()),
+            #[cfg(feature = "mars_photos")]
+            mars_photos: mars_photos::MarsPhotosApi::with_key_ring(
+                keys.clone(),
+                reqwest_client.clone(),
+                gateway_base.join(mars_photos::PATH)?,
+            ),
+            #[cfg(feature = "donki")]
+            donki: donki::DonkiApi::with_key_ring(
+                keys.clone(),
+                reqwest_client.clone(),
+                gateway_base.join(donki::PATH)?,
+            )
+            .with_clock(self.clock.clone()),
+            #[cfg(feature = "eonet")]
+            eonet: eonet::EonetApi::new(reqwest_client.clone()),
+            #[cfg(feature = "exoplanet")]
+            exoplanet: exoplanet::ExoplanetApi::new(reqwest_client.clone()),
+            #[cfg(feature = "insight")]
+            insight: insight::InsightApi::with_key_ring(
+                keys.clone(),
+                reqwest_client.clone(),
+                gateway_base.join(insight::PATH)?,
+            ),
+            #[cfg(feature = "image_library")]
+            image_library: image_library::ImageLibraryApi::new(reqwest_client.clone()),
+            api_token: self.api_token,
+            user_agent: self.user_agent,
+        })
     }
 }
 
@@ -62,6 +807,497 @@ mod tests {
         let token =
             std::env::var("API_TOKEN").expect("API_TOKEN must be set in .env or environment");
         let client = StellariaClient::new(&token);
-        assert_eq!(client.api_token, token);
+        assert_eq!(client.api_token.expose(), token);
+    }
+
+    #[test]
+    fn test_client_debug_redacts_token() {
+        let client = StellariaClient::new("SUPER_SECRET_KEY");
+
+        let debug = format!("{client:?}");
+        assert!(!debug.contains("SUPER_SECRET_KEY"));
+        assert!(debug.contains("***REDACTED***"));
+        assert_eq!(client.api_token.to_string(), "***REDACTED***");
+        assert_eq!(client.api_token.expose(), "SUPER_SECRET_KEY");
+    }
+
+    #[cfg(feature = "apod")]
+    fn api_error(code: u16) -> StellariaError {
+        let body = serde_json::json!({ "code": code, "msg": "error", "service_version": "v1" });
+        serde_json::from_value::<apod::ApodApiError>(body)
+            .unwrap()
+            .into()
+    }
+
+    #[cfg(feature = "apod")]
+    #[test]
+    fn test_error_classification() {
+        let rate_limited = api_error(429);
+        assert!(rate_limited.is_rate_limited());
+        assert!(rate_limited.is_retryable());
+        assert!(rate_limited.is_client_error());
+
+        let unavailable = api_error(503);
+        assert!(unavailable.is_retryable());
+        assert!(!unavailable.is_client_error());
+        assert!(!unavailable.is_rate_limited());
+
+        let forbidden = api_error(403);
+        assert!(forbidden.is_client_error());
+        assert!(!forbidden.is_retryable());
+
+        let params = apod::ApodParams::builder()
+            .date(chrono::NaiveDate::from_ymd_opt(1990, 1, 1).unwrap())
+            .build()
+            .unwrap_err();
+        assert!(params.is_client_error());
+        assert!(!params.is_retryable());
+        assert_eq!(params.status(), None);
+
+        let json: StellariaError = serde_json::from_str::<u8>("x").unwrap_err().into();
+        assert!(!json.is_retryable());
+        assert!(!json.is_client_error());
+    }
+
+    #[cfg(feature = "apod")]
+    #[tokio::test]
+    async fn test_gateway_base_routes_apod() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/nasa/planetary/apod"))
+            .and(query_param("api_key", "TEST_KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "date": "2024-12-12",
+                "explanation": "A test picture.",
+                "media_type": "image",
+                "service_version": "v1",
+                "title": "Gateway",
+                "url": "https://apod.nasa.gov/apod/image/test.jpg",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let gateway = test_util::mock_base_url(&server, "/nasa");
+        let client = StellariaClient::builder("TEST_KEY")
+            .gateway_base(gateway)
+            .build()
+            .unwrap();
+        let params = apod::ApodParams::builder()
+            .date(chrono::NaiveDate::from_ymd_opt(2024, 12, 12).unwrap())
+            .build()
+            .unwrap();
+
+        let resp = client.apod.get(params).await.unwrap();
+        assert_eq!(resp[0].title, "Gateway");
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[allow(dead_code)]
+    fn assert_send<T: Send>(_: &T) {}
+
+    // Fails to compile when a client stops being shareable across tasks or
+    // one of its futures holds a `!Send` value (e.g. a `MutexGuard`) across
+    // an await.
+    #[test]
+    fn test_clients_and_futures_are_send() {
+        assert_send_sync::<StellariaClient>();
+        assert_send_sync::<StellariaError>();
+
+        #[allow(unused_variables)]
+        let client = StellariaClient::new("TEST_KEY");
+
+        #[cfg(feature = "apod")]
+        {
+            assert_send(&client.probe_rate_limit());
+            let date = chrono::NaiveDate::from_ymd_opt(2024, 12, 12).unwrap();
+            let params = || apod::ApodParams::builder().build().unwrap();
+            assert_send(&client.apod.get(params()));
+            assert_send(&client.apod.get_apod(params()));
+            assert_send(&client.apod.get_summaries(params()));
+            assert_send(&client.apod.get_with_headers(params()));
+            assert_send(&client.apod.get_with_stats(params()));
+            assert_send(&client.apod.media_type_on(date));
+            assert_send(&client.apod.anniversaries(12, 12, 2020));
+            assert_send(&client.apod.find_missing_dates(date, date));
+
+            let response: apod::ApodResponse = serde_json::from_value(serde_json::json!({
+                "date": "2024-12-12",
+                "explanation": "",
+                "media_type": "image",
+                "service_version": "v1",
+                "title": "",
+                "url": "https://apod.nasa.gov/apod/image/test.jpg",
+            }))
+            .unwrap();
+            let reqwest_client = reqwest::Client::new();
+            let path = std::path::Path::new("apod.jpg");
+            assert_send(&response.dimensions(&reqwest_client));
+            let pref = apod::ImagePref::default();
+            assert_send(&response.download(&reqwest_client, pref));
+            assert_send(&response.download_to(&reqwest_client, pref, path));
+            assert_send(&client.apod.download(&response));
+            assert_send(&client.apod.download_to(&response, path));
+            assert_send(&client.apod.archive_to(date, path, &reqwest_client));
+            let responses = [response];
+            assert_send(&client.apod.fetch_previews(&responses, 4));
+        }
+        #[cfg(feature = "neows")]
+        assert_send(
+            &client
+                .neows
+                .get(neows::NeoWsParams::builder().build().unwrap()),
+        );
+        #[cfg(feature = "epic")]
+        {
+            let collection = epic::EpicCollection::Natural;
+            let date = chrono::NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+            assert_send(&client.epic.get(epic::EpicParams::default()));
+            assert_send(&client.epic.available(collection));
+            assert_send(&client.epic.is_available(collection, date));
+        }
+        #[cfg(feature = "mars_photos")]
+        {
+            let params = mars_photos::MarsPhotosParams::builder(mars_photos::Rover::Curiosity)
+                .build()
+                .unwrap();
+            assert_send(&client.mars_photos.get(params));
+            assert_send(&client.mars_photos.photos_all(params));
+        }
+        #[cfg(feature = "donki")]
+        {
+            let params = || {
+                donki::DonkiParams::builder(donki::DonkiEvent::Flr)
+                    .build()
+                    .unwrap()
+            };
+            assert_send(&client.donki.get(params()));
+            assert_send(&client.donki.timeline(params(), &[donki::DonkiEvent::Cme]));
+        }
+        #[cfg(feature = "eonet")]
+        assert_send(&client.eonet.get(eonet::EonetParams::default()));
+        #[cfg(feature = "exoplanet")]
+        assert_send(
+            &client
+                .exoplanet
+                .get(exoplanet::ExoplanetParams::builder("ps").build().unwrap()),
+        );
+        #[cfg(feature = "insight")]
+        assert_send(&client.insight.get(insight::InsightParams));
+        #[cfg(feature = "image_library")]
+        {
+            let params = || {
+                image_library::SearchParams::builder("moon")
+                    .build()
+                    .unwrap()
+            };
+            assert_send(&client.image_library.get(params()));
+            assert_send(&client.image_library.search_all(params()));
+        }
+    }
+
+    #[test]
+    fn test_default_user_agent() {
+        let client = StellariaClient::new("TEST_KEY");
+        assert_eq!(
+            client.user_agent,
+            format!("stellaria/{}", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn test_invalid_user_agent_is_an_error() {
+        let client = StellariaClient::with_user_agent("TEST_KEY", "my-gallery\n2.0");
+        assert!(matches!(client, Err(StellariaError::RequestError(_))));
+
+        let client = StellariaClient::with_user_agent("TEST_KEY", "my-gallery/2.0").unwrap();
+        assert_eq!(client.user_agent, "my-gallery/2.0");
+    }
+
+    #[cfg(feature = "apod")]
+    #[tokio::test]
+    async fn test_user_agent_sent_with_apod_request() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(header("user-agent", "my-gallery/2.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "date": "2024-12-12",
+                "explanation": "A test picture.",
+                "media_type": "image",
+                "service_version": "v1",
+                "title": "User agent",
+                "url": "https://apod.nasa.gov/apod/image/test.jpg",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = StellariaClient::builder("TEST_KEY")
+            .gateway_base(Url::parse(&server.uri()).unwrap())
+            .user_agent("my-gallery/2.0")
+            .build()
+            .unwrap();
+        let params = apod::ApodParams::builder().build().unwrap();
+
+        let resp = client.apod.get(params).await.unwrap();
+        assert_eq!(resp[0].title, "User agent");
+    }
+
+    #[cfg(feature = "blocking")]
+    #[tokio::test]
+    async fn test_blocking_get_apod() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("api_key", "TEST_KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "date": "2024-12-12",
+                "explanation": "A test picture.",
+                "media_type": "image",
+                "service_version": "v1",
+                "title": "Blocking",
+                "url": "https://apod.nasa.gov/apod/image/test.jpg",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // The blocking client must live outside the runtime's threads.
+        let gateway_base = Url::parse(&server.uri()).unwrap();
+        let responses = std::thread::spawn(move || {
+            let client = StellariaClient::builder("TEST_KEY")
+                .gateway_base(gateway_base)
+                .blocking()
+                .build()
+                .unwrap();
+            let params = apod::ApodParams::builder().build().unwrap();
+            client.apod.blocking_get(params)
+        })
+        .join()
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(responses[0].title, "Blocking");
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_blocking_body_shorter_than_content_length_is_truncated() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let partial = r#"[{"date": "2024-12-12", "title": "Cut of"#;
+        let server = std::thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let _ = socket.read(&mut [0u8; 1024]).unwrap();
+            write!(
+                socket,
+                "HTTP/1.1 200 OK\r\ncontent-length: 4096\r\n\r\n{partial}"
+            )
+            .unwrap();
+        });
+
+        let client = StellariaClient::builder("TEST_KEY")
+            .gateway_base(Url::parse(&format!("http://{addr}/")).unwrap())
+            .blocking()
+            .build()
+            .unwrap();
+        let params = apod::ApodParams::builder().count(2).build().unwrap();
+        let err = client.apod.blocking_get(params).unwrap_err();
+        server.join().unwrap();
+
+        assert!(matches!(
+            err.root(),
+            StellariaError::TruncatedResponse { bytes_read } if *bytes_read == partial.len()
+        ));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_blocking_get_needs_blocking_client() {
+        let client = StellariaClient::new("TEST_KEY");
+        let params = apod::ApodParams::builder().build().unwrap();
+
+        let err = client.apod.blocking_get(params).unwrap_err();
+        assert!(matches!(err, StellariaError::ConfigError(_)));
+    }
+
+    #[cfg(all(feature = "apod", feature = "rustls-tls"))]
+    #[tokio::test]
+    async fn test_rustls_client_makes_request() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "date": "2024-12-12",
+                "explanation": "A test picture.",
+                "media_type": "image",
+                "service_version": "v1",
+                "title": "Rustls",
+                "url": "https://apod.nasa.gov/apod/image/test.jpg",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let reqwest_client = reqwest::Client::builder().use_rustls_tls().build().unwrap();
+        let base_url = test_util::mock_base_url(&server, "/planetary/apod");
+        let apod = apod::ApodApi::with_base_url("TEST_KEY", reqwest_client, base_url).unwrap();
+        let params = apod::ApodParams::builder().count(1).build().unwrap();
+
+        let resp = apod.get(params).await.unwrap();
+        assert_eq!(resp[0].title, "Rustls");
+    }
+
+    #[cfg(feature = "apod")]
+    #[tokio::test]
+    async fn test_probe_rate_limit_reads_headers() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/planetary/apod"))
+            .and(query_param("count", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([{
+                        "date": "2024-12-12",
+                        "explanation": "A test picture.",
+                        "media_type": "image",
+                        "service_version": "v1",
+                        "title": "Probe",
+                        "url": "https://apod.nasa.gov/apod/image/test.jpg",
+                    }]))
+                    .insert_header("X-RateLimit-Limit", "1000")
+                    .insert_header("X-RateLimit-Remaining", "998"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = StellariaClient::builder("TEST_KEY")
+            .gateway_base(Url::parse(&server.uri()).unwrap())
+            .build()
+            .unwrap();
+
+        let rate_limit = client.probe_rate_limit().await.unwrap();
+        assert_eq!(
+            rate_limit,
+            RateLimit {
+                limit: Some(1000),
+                remaining: Some(998),
+            }
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_ignores_malformed_headers() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "lots".parse().unwrap());
+
+        let rate_limit = RateLimit::from_headers(&headers);
+        assert_eq!(rate_limit.limit, None);
+        assert_eq!(rate_limit.remaining, None);
+    }
+
+    #[test]
+    fn test_retry_after_seconds_or_http_date() {
+        use chrono::{TimeZone, Utc};
+        use std::time::Duration;
+
+        let now = Utc.with_ymd_and_hms(2015, 10, 21, 7, 26, 0).unwrap();
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[cfg(feature = "apod")]
+    #[test]
+    fn test_now_provider_freezes_date_defaults() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let frozen = Utc.with_ymd_and_hms(2020, 5, 5, 12, 0, 0).unwrap();
+        let client = StellariaClient::builder("TEST_KEY")
+            .now_provider(move || frozen)
+            .build()
+            .unwrap();
+        let frozen_date = NaiveDate::from_ymd_opt(2020, 5, 5).unwrap();
+
+        let params = client.apod.params().build().unwrap();
+        assert_eq!(params.date, Some(frozen_date));
+
+        let dates = client.apod.fetch_all_dates().unwrap();
+        assert_eq!(dates.last(), Some(&frozen_date));
+
+        let too_late = client.apod.params().date_ymd(2020, 5, 6).build();
+        assert!(too_late.is_err());
+
+        #[cfg(feature = "neows")]
+        {
+            let params = client.neows.params().build().unwrap();
+            assert_eq!(params.start_date, Some(frozen_date));
+        }
+
+        #[cfg(feature = "donki")]
+        {
+            let params = client.donki.params(donki::DonkiEvent::Cme).build().unwrap();
+            assert_eq!(params.start_date, NaiveDate::from_ymd_opt(2020, 4, 5));
+        }
+    }
+
+    #[test]
+    fn test_invalid_gateway_base_fails() {
+        let gateway = Url::parse("mailto:nasa@example.com").unwrap();
+        let result = StellariaClient::builder("TEST_KEY")
+            .gateway_base(gateway)
+            .build();
+
+        assert!(matches!(result, Err(StellariaError::UrlError(_))));
+    }
+
+    #[cfg(feature = "apod")]
+    #[test]
+    fn test_builder_rejects_oversized_chunk_days() {
+        let result = StellariaClient::builder("TEST_KEY")
+            .chunk_days(apod::MAX_CHUNK_DAYS + 1)
+            .build();
+
+        assert!(matches!(result, Err(StellariaError::ConfigError(_))));
+    }
+
+    #[cfg(feature = "apod")]
+    #[test]
+    fn test_minimal_features_apod_only() {
+        let client = StellariaClient::new("DEMO_KEY");
+        let params = apod::ApodParams::builder().count(1).build().unwrap();
+
+        let _: &apod::ApodApi = &client.apod;
+        assert_eq!(params.count, Some(1));
     }
 }
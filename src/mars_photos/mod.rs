@@ -0,0 +1,640 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod test;
+
+use chrono::NaiveDate;
+use futures::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use std::str::FromStr;
+use thiserror::Error;
+use url::Url;
+
+use crate::keys::KeyRing;
+use crate::pagination::{Page, paginate};
+use crate::request;
+use crate::{Api, ApiError, ApiToken, StellariaError, StellariaResult, date_serde};
+
+const BASE_URL: &str = "https://api.nasa.gov/mars-photos/api/v1/";
+pub(crate) const PATH: &str = "mars-photos/api/v1/";
+const ENDPOINT: &str = "Mars Rover Photos";
+/// How many photos NASA returns per page.
+const PAGE_SIZE: usize = 25;
+
+#[derive(Debug)]
+pub struct MarsPhotosApi {
+    keys: KeyRing,
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rover {
+    Curiosity,
+    Opportunity,
+    Spirit,
+    Perseverance,
+}
+
+impl Rover {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Rover::Curiosity => "curiosity",
+            Rover::Opportunity => "opportunity",
+            Rover::Spirit => "spirit",
+            Rover::Perseverance => "perseverance",
+        }
+    }
+}
+
+impl Rover {
+    /// The cameras NASA lists photos from for this rover.
+    pub fn cameras(&self) -> &'static [RoverCamera] {
+        use RoverCamera::*;
+        match self {
+            Rover::Curiosity => &[Fhaz, Rhaz, Mast, Chemcam, Mahli, Mardi, Navcam],
+            Rover::Opportunity | Rover::Spirit => &[Fhaz, Rhaz, Navcam, Pancam, Minites],
+            Rover::Perseverance => &[
+                EdlRucam,
+                EdlRdcam,
+                EdlDdcam,
+                EdlPucam1,
+                EdlPucam2,
+                NavcamLeft,
+                NavcamRight,
+                MczLeft,
+                MczRight,
+                FrontHazcamLeftA,
+                FrontHazcamRightA,
+                RearHazcamLeft,
+                RearHazcamRight,
+                Skycam,
+                SherlocWatson,
+                SupercamRmi,
+                Lcam,
+                Cachecam,
+            ],
+        }
+    }
+}
+
+impl FromStr for Rover {
+    type Err = MarsPhotosError;
+
+    /// Parses a rover name, ignoring case, e.g. `"Curiosity"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Rover::Curiosity,
+            Rover::Opportunity,
+            Rover::Spirit,
+            Rover::Perseverance,
+        ]
+        .into_iter()
+        .find(|rover| rover.as_str().eq_ignore_ascii_case(s.trim()))
+        .ok_or_else(|| MarsPhotosError::UnknownRover(s.to_string()))
+    }
+}
+
+impl TryFrom<&str> for Rover {
+    type Error = MarsPhotosError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Cameras the photos can be filtered by. Not every rover carries every
+/// camera, see [`Rover::cameras`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoverCamera {
+    /// Front Hazard Avoidance Camera.
+    Fhaz,
+    /// Rear Hazard Avoidance Camera.
+    Rhaz,
+    /// Mast Camera.
+    Mast,
+    /// Chemistry and Camera Complex.
+    Chemcam,
+    /// Mars Hand Lens Imager.
+    Mahli,
+    /// Mars Descent Imager.
+    Mardi,
+    /// Navigation Camera.
+    Navcam,
+    /// Panoramic Camera.
+    Pancam,
+    /// Miniature Thermal Emission Spectrometer (Mini-TES).
+    Minites,
+    /// Rover Up-Look Camera.
+    EdlRucam,
+    /// Rover Down-Look Camera.
+    EdlRdcam,
+    /// Descent Stage Down-Look Camera.
+    EdlDdcam,
+    /// Parachute Up-Look Camera A.
+    EdlPucam1,
+    /// Parachute Up-Look Camera B.
+    EdlPucam2,
+    /// Navigation Camera - Left.
+    NavcamLeft,
+    /// Navigation Camera - Right.
+    NavcamRight,
+    /// Mast Camera Zoom - Left.
+    MczLeft,
+    /// Mast Camera Zoom - Right.
+    MczRight,
+    /// Front Hazard Avoidance Camera - Left.
+    FrontHazcamLeftA,
+    /// Front Hazard Avoidance Camera - Right.
+    FrontHazcamRightA,
+    /// Rear Hazard Avoidance Camera - Left.
+    RearHazcamLeft,
+    /// Rear Hazard Avoidance Camera - Right.
+    RearHazcamRight,
+    /// MEDA Skycam.
+    Skycam,
+    /// SHERLOC WATSON Camera.
+    SherlocWatson,
+    /// SuperCam Remote Micro Imager.
+    SupercamRmi,
+    /// Lander Vision System Camera.
+    Lcam,
+    /// CacheCam.
+    Cachecam,
+}
+
+impl RoverCamera {
+    pub const ALL: [RoverCamera; 27] = [
+        RoverCamera::Fhaz,
+        RoverCamera::Rhaz,
+        RoverCamera::Mast,
+        RoverCamera::Chemcam,
+        RoverCamera::Mahli,
+        RoverCamera::Mardi,
+        RoverCamera::Navcam,
+        RoverCamera::Pancam,
+        RoverCamera::Minites,
+        RoverCamera::EdlRucam,
+        RoverCamera::EdlRdcam,
+        RoverCamera::EdlDdcam,
+        RoverCamera::EdlPucam1,
+        RoverCamera::EdlPucam2,
+        RoverCamera::NavcamLeft,
+        RoverCamera::NavcamRight,
+        RoverCamera::MczLeft,
+        RoverCamera::MczRight,
+        RoverCamera::FrontHazcamLeftA,
+        RoverCamera::FrontHazcamRightA,
+        RoverCamera::RearHazcamLeft,
+        RoverCamera::RearHazcamRight,
+        RoverCamera::Skycam,
+        RoverCamera::SherlocWatson,
+        RoverCamera::SupercamRmi,
+        RoverCamera::Lcam,
+        RoverCamera::Cachecam,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RoverCamera::Fhaz => "fhaz",
+            RoverCamera::Rhaz => "rhaz",
+            RoverCamera::Mast => "mast",
+            RoverCamera::Chemcam => "chemcam",
+            RoverCamera::Mahli => "mahli",
+            RoverCamera::Mardi => "mardi",
+            RoverCamera::Navcam => "navcam",
+            RoverCamera::Pancam => "pancam",
+            RoverCamera::Minites => "minites",
+            RoverCamera::EdlRucam => "edl_rucam",
+            RoverCamera::EdlRdcam => "edl_rdcam",
+            RoverCamera::EdlDdcam => "edl_ddcam",
+            RoverCamera::EdlPucam1 => "edl_pucam1",
+            RoverCamera::EdlPucam2 => "edl_pucam2",
+            RoverCamera::NavcamLeft => "navcam_left",
+            RoverCamera::NavcamRight => "navcam_right",
+            RoverCamera::MczLeft => "mcz_left",
+            RoverCamera::MczRight => "mcz_right",
+            RoverCamera::FrontHazcamLeftA => "front_hazcam_left_a",
+            RoverCamera::FrontHazcamRightA => "front_hazcam_right_a",
+            RoverCamera::RearHazcamLeft => "rear_hazcam_left",
+            RoverCamera::RearHazcamRight => "rear_hazcam_right",
+            RoverCamera::Skycam => "skycam",
+            RoverCamera::SherlocWatson => "sherloc_watson",
+            RoverCamera::SupercamRmi => "supercam_rmi",
+            RoverCamera::Lcam => "lcam",
+            RoverCamera::Cachecam => "cachecam",
+        }
+    }
+
+    fn full_name(&self) -> &'static str {
+        match self {
+            RoverCamera::Fhaz => "Front Hazard Avoidance Camera",
+            RoverCamera::Rhaz => "Rear Hazard Avoidance Camera",
+            RoverCamera::Mast => "Mast Camera",
+            RoverCamera::Chemcam => "Chemistry and Camera Complex",
+            RoverCamera::Mahli => "Mars Hand Lens Imager",
+            RoverCamera::Mardi => "Mars Descent Imager",
+            RoverCamera::Navcam => "Navigation Camera",
+            RoverCamera::Pancam => "Panoramic Camera",
+            RoverCamera::Minites => "Miniature Thermal Emission Spectrometer (Mini-TES)",
+            RoverCamera::EdlRucam => "Rover Up-Look Camera",
+            RoverCamera::EdlRdcam => "Rover Down-Look Camera",
+            RoverCamera::EdlDdcam => "Descent Stage Down-Look Camera",
+            RoverCamera::EdlPucam1 => "Parachute Up-Look Camera A",
+            RoverCamera::EdlPucam2 => "Parachute Up-Look Camera B",
+            RoverCamera::NavcamLeft => "Navigation Camera - Left",
+            RoverCamera::NavcamRight => "Navigation Camera - Right",
+            RoverCamera::MczLeft => "Mast Camera Zoom - Left",
+            RoverCamera::MczRight => "Mast Camera Zoom - Right",
+            RoverCamera::FrontHazcamLeftA => "Front Hazard Avoidance Camera - Left",
+            RoverCamera::FrontHazcamRightA => "Front Hazard Avoidance Camera - Right",
+            RoverCamera::RearHazcamLeft => "Rear Hazard Avoidance Camera - Left",
+            RoverCamera::RearHazcamRight => "Rear Hazard Avoidance Camera - Right",
+            RoverCamera::Skycam => "MEDA Skycam",
+            RoverCamera::SherlocWatson => "SHERLOC WATSON Camera",
+            RoverCamera::SupercamRmi => "SuperCam Remote Micro Imager",
+            RoverCamera::Lcam => "Lander Vision System Camera",
+            RoverCamera::Cachecam => "CacheCam",
+        }
+    }
+}
+
+impl FromStr for RoverCamera {
+    type Err = MarsPhotosError;
+
+    /// Parses either the abbreviation (`"FHAZ"`) or the full name
+    /// (`"Front Hazard Avoidance Camera"`), ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        RoverCamera::ALL
+            .into_iter()
+            .find(|camera| {
+                camera.as_str().eq_ignore_ascii_case(name)
+                    || camera.full_name().eq_ignore_ascii_case(name)
+            })
+            .ok_or_else(|| MarsPhotosError::UnknownCamera(s.to_string()))
+    }
+}
+
+impl TryFrom<&str> for RoverCamera {
+    type Error = MarsPhotosError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// The day to list photos for, either as a Martian sol or an Earth date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarsDay {
+    Sol(u32),
+    EarthDate(NaiveDate),
+}
+
+/// Query for a rover's photos. Without a [`MarsDay`] NASA returns the
+/// rover's latest photos.
+#[derive(Debug, Clone, Copy)]
+pub struct MarsPhotosParams {
+    pub rover: Rover,
+    pub day: Option<MarsDay>,
+    pub camera: Option<RoverCamera>,
+    /// 1-based; NASA returns 25 photos per page, and every photo when unset.
+    pub page: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct MarsPhotosParamsBuilder {
+    rover: Rover,
+    sol: Option<u32>,
+    earth_date: Option<NaiveDate>,
+    camera: Option<RoverCamera>,
+    page: Option<u32>,
+}
+
+impl MarsPhotosParamsBuilder {
+    pub fn sol(mut self, sol: u32) -> Self {
+        self.sol = Some(sol);
+        self
+    }
+
+    pub fn earth_date(mut self, earth_date: NaiveDate) -> Self {
+        self.earth_date = Some(earth_date);
+        self
+    }
+
+    pub fn camera(mut self, camera: RoverCamera) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    pub fn page(mut self, page: u32) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<MarsPhotosParams> {
+        let day = match (self.sol, self.earth_date) {
+            (Some(_), Some(_)) => {
+                return Err(MarsPhotosError::MarsPhotosParamsError(
+                    "sol and earth_date are mutually exclusive".to_string(),
+                )
+                .into());
+            }
+            (Some(sol), None) => Some(MarsDay::Sol(sol)),
+            (None, Some(earth_date)) => Some(MarsDay::EarthDate(earth_date)),
+            (None, None) => None,
+        };
+        if let Some(camera) = self.camera
+            && !self.rover.cameras().contains(&camera)
+        {
+            return Err(MarsPhotosError::MarsPhotosParamsError(format!(
+                "{} has no {} camera",
+                self.rover.as_str(),
+                camera.as_str()
+            ))
+            .into());
+        }
+        if self.page == Some(0) {
+            return Err(MarsPhotosError::MarsPhotosParamsError(
+                "Page numbers start at 1".to_string(),
+            )
+            .into());
+        }
+
+        Ok(MarsPhotosParams {
+            rover: self.rover,
+            day,
+            camera: self.camera,
+            page: self.page,
+        })
+    }
+}
+
+impl MarsPhotosParams {
+    pub fn builder(rover: Rover) -> MarsPhotosParamsBuilder {
+        MarsPhotosParamsBuilder {
+            rover,
+            sol: None,
+            earth_date: None,
+            camera: None,
+            page: None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        let mut description = match self.day {
+            Some(MarsDay::Sol(sol)) => format!("{} photos on sol {sol}", self.rover.as_str()),
+            Some(MarsDay::EarthDate(date)) => format!("{} photos on {date}", self.rover.as_str()),
+            None => format!("latest {} photos", self.rover.as_str()),
+        };
+        if let Some(camera) = self.camera {
+            description.push_str(&format!(" from {}", camera.as_str()));
+        }
+        if let Some(page) = self.page {
+            description.push_str(&format!(", page {page}"));
+        }
+        description
+    }
+
+    fn path(&self) -> String {
+        let endpoint = match self.day {
+            Some(_) => "photos",
+            None => "latest_photos",
+        };
+        format!("rovers/{}/{endpoint}", self.rover.as_str())
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        match self.day {
+            Some(MarsDay::Sol(sol)) => query.push(("sol", sol.to_string())),
+            Some(MarsDay::EarthDate(date)) => {
+                query.push(("earth_date", date.format(date_serde::FORMAT).to_string()))
+            }
+            None => {}
+        }
+        if let Some(camera) = self.camera {
+            query.push(("camera", camera.as_str().to_string()));
+        }
+        if let Some(page) = self.page {
+            query.push(("page", page.to_string()));
+        }
+        query
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MarsPhotosResponse {
+    #[serde(alias = "latest_photos")]
+    pub photos: Vec<MarsPhoto>,
+}
+
+impl MarsPhotosResponse {
+    /// The photos taken by `camera`, for filtering a response fetched across
+    /// all cameras.
+    pub fn photos_from_camera(&self, camera: RoverCamera) -> Vec<&MarsPhoto> {
+        self.photos
+            .iter()
+            .filter(|photo| photo.camera.name.eq_ignore_ascii_case(camera.as_str()))
+            .collect()
+    }
+}
+
+/// One numbered page of photos, for [`MarsPhotosApi::photos_all`].
+struct PhotoPage {
+    page: u32,
+    photos: Vec<MarsPhoto>,
+}
+
+impl Page for PhotoPage {
+    type Item = MarsPhoto;
+    type Request = u32;
+
+    /// NASA doesn't say how many pages there are, so a short page is the last.
+    fn next_request(&self) -> Option<u32> {
+        (self.photos.len() >= PAGE_SIZE).then_some(self.page + 1)
+    }
+
+    fn items(self) -> Vec<MarsPhoto> {
+        self.photos
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct MarsPhoto {
+    pub id: u64,
+    pub sol: u32,
+    pub camera: CameraInfo,
+    pub img_src: Url,
+    #[serde(deserialize_with = "date_serde::deserialize_date")]
+    pub earth_date: NaiveDate,
+    pub rover: RoverInfo,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CameraInfo {
+    pub id: u32,
+    /// The abbreviation, e.g. `FHAZ`.
+    pub name: String,
+    pub rover_id: u32,
+    pub full_name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RoverInfo {
+    pub id: u32,
+    pub name: String,
+    #[serde(deserialize_with = "date_serde::deserialize_date")]
+    pub landing_date: NaiveDate,
+    #[serde(deserialize_with = "date_serde::deserialize_date")]
+    pub launch_date: NaiveDate,
+    pub status: String,
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct MarsPhotosApiError {
+    code: u16,
+    msg: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MarsPhotosError {
+    #[error(transparent)]
+    MarsPhotosApiError(#[from] MarsPhotosApiError),
+    #[error("invalid parameters: {0}")]
+    MarsPhotosParamsError(String),
+    #[error("unknown rover: {0:?}")]
+    UnknownRover(String),
+    #[error("unknown camera: {0:?}")]
+    UnknownCamera(String),
+}
+
+impl MarsPhotosError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            MarsPhotosError::MarsPhotosApiError(err) => Some(err.code),
+            MarsPhotosError::MarsPhotosParamsError(_)
+            | MarsPhotosError::UnknownRover(_)
+            | MarsPhotosError::UnknownCamera(_) => None,
+        }
+    }
+}
+
+impl MarsPhotosApi {
+    pub fn new(api_key: impl Into<ApiToken>, reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default Mars Photos base url is valid");
+        Self::with_key_ring(KeyRing::new(api_key.into(), []), reqwest_client, base_url)
+    }
+
+    /// Like [`MarsPhotosApi::new`], but sends requests below `base_url`
+    /// instead of NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(
+        api_key: impl Into<ApiToken>,
+        reqwest_client: reqwest::Client,
+        mut base_url: Url,
+    ) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        Ok(Self::with_key_ring(
+            KeyRing::new(api_key.into(), []),
+            reqwest_client,
+            base_url,
+        ))
+    }
+
+    pub(crate) fn with_key_ring(
+        keys: KeyRing,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> Self {
+        Self {
+            keys,
+            base_url,
+            reqwest_client,
+        }
+    }
+
+    /// Extra api_keys to rotate to when the active key gets a 429.
+    pub fn with_fallback_keys<T: Into<ApiToken>>(
+        mut self,
+        fallback_keys: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let fallback_keys = fallback_keys.into_iter().map(Into::into);
+        self.keys = KeyRing::new(self.keys.primary().clone(), fallback_keys);
+        self
+    }
+
+    /// Streams the photos of every page from `params.page` on, or from the
+    /// first page when unset, fetching each page once the previous one is
+    /// used up.
+    ///
+    /// The stream ends after the first failed page.
+    pub fn photos_all(
+        &self,
+        params: MarsPhotosParams,
+    ) -> impl Stream<Item = StellariaResult<MarsPhoto>> + '_ {
+        let first = params.page.unwrap_or(1);
+        stream::once(self.get_page(params, first)).flat_map(move |first_page| match first_page {
+            Ok(first_page) => {
+                paginate(first_page, move |page| self.get_page(params, page)).left_stream()
+            }
+            Err(err) => stream::iter([Err(err)]).right_stream(),
+        })
+    }
+
+    async fn get_page(&self, params: MarsPhotosParams, page: u32) -> StellariaResult<PhotoPage> {
+        let params = MarsPhotosParams {
+            page: Some(page),
+            ..params
+        };
+        let response = self.get(params).await?;
+        Ok(PhotoPage {
+            page,
+            photos: response.photos,
+        })
+    }
+}
+
+impl Api for MarsPhotosApi {
+    type Params = MarsPhotosParams;
+    type Response = MarsPhotosResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        async {
+            let url = self.base_url.join(&params.path())?;
+            request::get_json(
+                &self.reqwest_client,
+                &url,
+                &params.query(),
+                Some(&self.keys),
+                ENDPOINT,
+                |code, body| MarsPhotosApiError {
+                    code,
+                    msg: request::error_message(body),
+                },
+            )
+            .await
+        }
+        .await
+        .map_err(|err| err.with_context(ENDPOINT, params.describe()))
+    }
+}
+
+impl From<MarsPhotosApiError> for StellariaError {
+    fn from(err: MarsPhotosApiError) -> Self {
+        MarsPhotosError::MarsPhotosApiError(err).into()
+    }
+}
+
+impl From<MarsPhotosError> for StellariaError {
+    fn from(err: MarsPhotosError) -> Self {
+        ApiError::MarsPhotosError(err).into()
+    }
+}
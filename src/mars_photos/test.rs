@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::mars_photos::{
+        MarsDay, MarsPhotosApi, MarsPhotosError, MarsPhotosParams, MarsPhotosResponse, Rover,
+        RoverCamera,
+    };
+    use crate::test_util::{TEST_KEY, mock_base_url};
+    use crate::{Api, ApiError, StellariaError};
+
+    use chrono::NaiveDate;
+    use futures::StreamExt;
+    use reqwest::Client;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_mars_photos(server: &MockServer) -> MarsPhotosApi {
+        MarsPhotosApi::with_base_url(
+            TEST_KEY,
+            Client::new(),
+            mock_base_url(server, "/mars-photos/api/v1"),
+        )
+        .unwrap()
+    }
+
+    fn photo_json() -> serde_json::Value {
+        json!({
+            "id": 102693,
+            "sol": 1000,
+            "camera": {
+                "id": 20,
+                "name": "FHAZ",
+                "rover_id": 5,
+                "full_name": "Front Hazard Avoidance Camera",
+            },
+            "img_src": "http://mars.jpl.nasa.gov/msl-raw-images/proj/msl/redops/ods/surface/sol/01000/opgs/edr/fcam/FLB_486265257EDR_F0481570FHAZ00323M_.JPG",
+            "earth_date": "2015-05-30",
+            "rover": {
+                "id": 5,
+                "name": "Curiosity",
+                "landing_date": "2012-08-06",
+                "launch_date": "2011-11-26",
+                "status": "active",
+            },
+        })
+    }
+
+    fn photos_json(count: u64) -> serde_json::Value {
+        let photos: Vec<_> = (0..count)
+            .map(|id| {
+                let mut photo = photo_json();
+                photo["id"] = json!(id);
+                photo
+            })
+            .collect();
+        json!({ "photos": photos })
+    }
+
+    // ==================== Response Tests ====================
+
+    #[test]
+    fn test_photos_from_camera_filters_mixed_response() {
+        let mut navcam = photo_json();
+        navcam["id"] = json!(424926);
+        navcam["camera"] = json!({
+            "id": 26,
+            "name": "NAVCAM",
+            "rover_id": 5,
+            "full_name": "Navigation Camera",
+        });
+        let mut second_fhaz = photo_json();
+        second_fhaz["id"] = json!(102694);
+        let response: MarsPhotosResponse =
+            serde_json::from_value(json!({"photos": [photo_json(), navcam, second_fhaz]})).unwrap();
+
+        let ids = |camera| -> Vec<u64> {
+            response
+                .photos_from_camera(camera)
+                .iter()
+                .map(|photo| photo.id)
+                .collect()
+        };
+        assert_eq!(ids(RoverCamera::Fhaz), [102693, 102694]);
+        assert_eq!(ids(RoverCamera::Navcam), [424926]);
+        assert!(ids(RoverCamera::Mast).is_empty());
+    }
+
+    // ==================== Params Tests ====================
+
+    #[test]
+    fn test_builder_rejects_sol_and_earth_date() {
+        let err = MarsPhotosParams::builder(Rover::Curiosity)
+            .sol(1000)
+            .earth_date(NaiveDate::from_ymd_opt(2015, 5, 30).unwrap())
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            StellariaError::ApiError(ApiError::MarsPhotosError(
+                MarsPhotosError::MarsPhotosParamsError(_)
+            ))
+        ));
+        assert!(err.is_client_error());
+    }
+
+    #[test]
+    fn test_builder_accepts_either_day() {
+        let date = NaiveDate::from_ymd_opt(2015, 5, 30).unwrap();
+        let by_sol = MarsPhotosParams::builder(Rover::Curiosity)
+            .sol(1000)
+            .build()
+            .unwrap();
+        let by_date = MarsPhotosParams::builder(Rover::Spirit)
+            .earth_date(date)
+            .build()
+            .unwrap();
+
+        assert_eq!(by_sol.day, Some(MarsDay::Sol(1000)));
+        assert_eq!(by_date.day, Some(MarsDay::EarthDate(date)));
+    }
+
+    #[test]
+    fn test_builder_rejects_camera_the_rover_lacks() {
+        let err = MarsPhotosParams::builder(Rover::Curiosity)
+            .camera(RoverCamera::Pancam)
+            .build()
+            .unwrap_err();
+        assert!(err.is_client_error());
+
+        let err = MarsPhotosParams::builder(Rover::Perseverance)
+            .camera(RoverCamera::Fhaz)
+            .build()
+            .unwrap_err();
+        assert!(err.is_client_error());
+
+        for rover in [Rover::Curiosity, Rover::Spirit, Rover::Perseverance] {
+            for &camera in rover.cameras() {
+                let params = MarsPhotosParams::builder(rover)
+                    .camera(camera)
+                    .build()
+                    .unwrap();
+                assert_eq!(params.camera, Some(camera));
+            }
+        }
+    }
+
+    #[test]
+    fn test_builder_rejects_page_zero() {
+        let err = MarsPhotosParams::builder(Rover::Curiosity)
+            .page(0)
+            .build()
+            .unwrap_err();
+        assert!(err.is_client_error());
+    }
+
+    // ==================== Parsing Tests ====================
+
+    #[test]
+    fn test_rover_parses_ignoring_case() {
+        assert_eq!("curiosity".parse::<Rover>().unwrap(), Rover::Curiosity);
+        assert_eq!(
+            Rover::try_from("PERSEVERANCE").unwrap(),
+            Rover::Perseverance
+        );
+        assert_eq!("Spirit".parse::<Rover>().unwrap(), Rover::Spirit);
+    }
+
+    #[test]
+    fn test_camera_parses_abbreviation_and_full_name() {
+        assert_eq!("FHAZ".parse::<RoverCamera>().unwrap(), RoverCamera::Fhaz);
+        assert_eq!(
+            RoverCamera::try_from("navigation camera").unwrap(),
+            RoverCamera::Navcam
+        );
+        assert_eq!(
+            "Mars Hand Lens Imager".parse::<RoverCamera>().unwrap(),
+            RoverCamera::Mahli
+        );
+        assert_eq!(
+            "NAVCAM_LEFT".parse::<RoverCamera>().unwrap(),
+            RoverCamera::NavcamLeft
+        );
+        assert_eq!(
+            "MEDA Skycam".parse::<RoverCamera>().unwrap(),
+            RoverCamera::Skycam
+        );
+    }
+
+    #[test]
+    fn test_unknown_rover_and_camera_error() {
+        assert!(matches!(
+            "sojourner".parse::<Rover>(),
+            Err(MarsPhotosError::UnknownRover(name)) if name == "sojourner"
+        ));
+        assert!(matches!(
+            RoverCamera::try_from("hazcam"),
+            Err(MarsPhotosError::UnknownCamera(name)) if name == "hazcam"
+        ));
+
+        let err: StellariaError = MarsPhotosError::UnknownRover("sojourner".into()).into();
+        assert!(err.is_client_error());
+    }
+
+    // ==================== API Tests ====================
+
+    #[tokio::test]
+    async fn test_get_photos_by_sol_and_camera() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/mars-photos/api/v1/rovers/curiosity/photos"))
+            .and(query_param("sol", "1000"))
+            .and(query_param("camera", "fhaz"))
+            .and(query_param("api_key", "TEST_KEY"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"photos": [photo_json()]})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = MarsPhotosParams::builder(Rover::Curiosity)
+            .sol(1000)
+            .camera(RoverCamera::Fhaz)
+            .build()
+            .unwrap();
+        let response = mock_mars_photos(&server).get(params).await.unwrap();
+
+        assert_eq!(response.photos.len(), 1);
+        let photo = &response.photos[0];
+        assert_eq!(photo.camera.name, "FHAZ");
+        assert_eq!(photo.rover.name, "Curiosity");
+        assert_eq!(
+            photo.earth_date,
+            NaiveDate::from_ymd_opt(2015, 5, 30).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_photos_without_day() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/mars-photos/api/v1/rovers/curiosity/latest_photos"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"latest_photos": [photo_json()]})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = MarsPhotosParams::builder(Rover::Curiosity).build().unwrap();
+        let response = mock_mars_photos(&server).get(params).await.unwrap();
+
+        assert_eq!(response.photos.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_perseverance_page() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/mars-photos/api/v1/rovers/perseverance/photos"))
+            .and(query_param("sol", "100"))
+            .and(query_param("camera", "mcz_right"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"photos": []})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = MarsPhotosParams::builder(Rover::Perseverance)
+            .sol(100)
+            .camera(RoverCamera::MczRight)
+            .page(2)
+            .build()
+            .unwrap();
+        let response = mock_mars_photos(&server).get(params).await.unwrap();
+
+        assert!(response.photos.is_empty());
+    }
+
+    // ==================== Pagination Tests ====================
+
+    #[tokio::test]
+    async fn test_photos_all_stops_after_short_page() {
+        let server = MockServer::start().await;
+
+        for (page, count) in [("1", 25), ("2", 3)] {
+            Mock::given(method("GET"))
+                .and(path("/mars-photos/api/v1/rovers/curiosity/photos"))
+                .and(query_param("sol", "1000"))
+                .and(query_param("page", page))
+                .respond_with(ResponseTemplate::new(200).set_body_json(photos_json(count)))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let params = MarsPhotosParams::builder(Rover::Curiosity)
+            .sol(1000)
+            .build()
+            .unwrap();
+        let ids: Vec<_> = mock_mars_photos(&server)
+            .photos_all(params)
+            .map(|photo| photo.unwrap().id)
+            .collect()
+            .await;
+
+        assert_eq!(ids.len(), 28);
+        assert_eq!(ids[25..], [0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_photos_all_starts_at_params_page_and_ends_on_error() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(query_param("page", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(photos_json(25)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("page", "4"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("server error"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = MarsPhotosParams::builder(Rover::Curiosity)
+            .sol(1000)
+            .page(3)
+            .build()
+            .unwrap();
+        let photos: Vec<_> = mock_mars_photos(&server).photos_all(params).collect().await;
+
+        assert_eq!(photos.len(), 26);
+        assert_eq!(photos[25].as_ref().unwrap_err().status(), Some(500));
+    }
+}
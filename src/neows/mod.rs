@@ -0,0 +1,383 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+mod params;
+
+#[cfg(test)]
+mod test;
+
+use std::collections::{BTreeMap, btree_map};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+use url::Url;
+
+use crate::keys::KeyRing;
+use crate::request;
+use crate::serde_util::{de_str_or_bool, de_str_or_num_f64};
+use crate::{Api, ApiError, ApiToken, Clock, StellariaError, StellariaResult, date_serde};
+pub use params::{MAX_FEED_DAYS, NeoWsParams, NeoWsParamsBuilder};
+
+const BASE_URL: &str = "https://api.nasa.gov/neo/rest/v1/feed";
+pub(crate) const PATH: &str = "neo/rest/v1/feed";
+const ENDPOINT: &str = "NeoWs";
+
+#[derive(Debug)]
+pub struct NeoWsApi {
+    keys: KeyRing,
+    base_url: Url,
+    reqwest_client: reqwest::Client,
+    clock: Clock,
+}
+
+/// Near earth objects with a close approach in the requested window.
+#[derive(Deserialize, Debug, Clone)]
+pub struct NeoWsResponse {
+    /// The query that produced this feed, with the api_key removed.
+    #[serde(rename = "links", default, deserialize_with = "deserialize_self_link")]
+    pub self_link: Option<Url>,
+    pub element_count: u32,
+    pub near_earth_objects: BTreeMap<NaiveDate, Vec<NearEarthObject>>,
+}
+
+#[derive(Deserialize)]
+struct FeedLinks {
+    #[serde(rename = "self")]
+    self_link: Option<Url>,
+}
+
+fn deserialize_self_link<'de, D>(deserializer: D) -> Result<Option<Url>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut self_link = FeedLinks::deserialize(deserializer)?.self_link;
+    if let Some(url) = &mut self_link {
+        request::strip_api_key(url);
+    }
+    Ok(self_link)
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NearEarthObject {
+    pub id: String,
+    pub neo_reference_id: String,
+    pub name: String,
+    pub nasa_jpl_url: Url,
+    pub absolute_magnitude_h: f64,
+    /// Missing for a few poorly characterized objects.
+    #[serde(default)]
+    pub estimated_diameter: Option<EstimatedDiameter>,
+    #[serde(deserialize_with = "de_str_or_bool")]
+    pub is_potentially_hazardous_asteroid: bool,
+    pub close_approach_data: Vec<CloseApproach>,
+    #[serde(deserialize_with = "de_str_or_bool")]
+    pub is_sentry_object: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct EstimatedDiameter {
+    pub kilometers: DiameterRange,
+    pub meters: DiameterRange,
+    pub miles: DiameterRange,
+    pub feet: DiameterRange,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DiameterRange {
+    pub estimated_diameter_min: f64,
+    pub estimated_diameter_max: f64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CloseApproach {
+    #[serde(deserialize_with = "date_serde::deserialize_date")]
+    pub close_approach_date: NaiveDate,
+    pub close_approach_date_full: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub epoch_date_close_approach: i64,
+    pub relative_velocity: RelativeVelocity,
+    pub miss_distance: MissDistance,
+    pub orbiting_body: OrbitingBody,
+}
+
+/// The body a close approach is measured against, in NeoWs' abbreviations.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String")]
+pub enum OrbitingBody {
+    Earth,
+    Moon,
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+    /// Any body not listed above, as NeoWs names it.
+    Other(String),
+}
+
+impl From<String> for OrbitingBody {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "Earth" => OrbitingBody::Earth,
+            "Moon" => OrbitingBody::Moon,
+            "Merc" => OrbitingBody::Mercury,
+            "Venus" => OrbitingBody::Venus,
+            "Mars" => OrbitingBody::Mars,
+            "Juptr" => OrbitingBody::Jupiter,
+            "Satrn" => OrbitingBody::Saturn,
+            "Urnus" => OrbitingBody::Uranus,
+            "Neptn" => OrbitingBody::Neptune,
+            _ => OrbitingBody::Other(name),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RelativeVelocity {
+    #[serde(deserialize_with = "de_str_or_num_f64")]
+    pub kilometers_per_second: f64,
+    #[serde(deserialize_with = "de_str_or_num_f64")]
+    pub kilometers_per_hour: f64,
+    #[serde(deserialize_with = "de_str_or_num_f64")]
+    pub miles_per_hour: f64,
+}
+
+impl RelativeVelocity {
+    /// Kilometers per second.
+    pub fn kps(&self) -> f64 {
+        self.kilometers_per_second
+    }
+
+    /// Kilometers per hour.
+    pub fn kph(&self) -> f64 {
+        self.kilometers_per_hour
+    }
+
+    /// Miles per hour.
+    pub fn mph(&self) -> f64 {
+        self.miles_per_hour
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct MissDistance {
+    #[serde(deserialize_with = "de_str_or_num_f64")]
+    pub astronomical: f64,
+    #[serde(deserialize_with = "de_str_or_num_f64")]
+    pub lunar: f64,
+    #[serde(deserialize_with = "de_str_or_num_f64")]
+    pub kilometers: f64,
+    #[serde(deserialize_with = "de_str_or_num_f64")]
+    pub miles: f64,
+}
+
+#[derive(Debug, Error)]
+#[error("http code {code}: {msg}")]
+pub struct NeoWsApiError {
+    code: u16,
+    msg: String,
+}
+
+/// The body NeoWs itself sends for rejected feed queries.
+#[derive(Deserialize)]
+struct NeoWsErrorBody {
+    error_message: String,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum NeoWsError {
+    #[error(transparent)]
+    NeoWsApiError(#[from] NeoWsApiError),
+    #[error("invalid parameters: {0}")]
+    NeoWsParamsError(String),
+}
+
+impl NearEarthObject {
+    /// The close approaches measured against Earth, leaving out those
+    /// relative to other bodies.
+    pub fn earth_approaches(&self) -> Vec<&CloseApproach> {
+        self.close_approach_data
+            .iter()
+            .filter(|approach| approach.orbiting_body == OrbitingBody::Earth)
+            .collect()
+    }
+
+    /// The close approach with the highest relative velocity, or `None`
+    /// without close approach data.
+    pub fn fastest_approach(&self) -> Option<&CloseApproach> {
+        self.close_approach_data.iter().max_by(|a, b| {
+            a.relative_velocity
+                .kps()
+                .total_cmp(&b.relative_velocity.kps())
+        })
+    }
+}
+
+impl NeoWsResponse {
+    /// Objects whose estimated maximum diameter is below `km`. Objects NeoWs
+    /// has no diameter for are left out rather than treated as tiny.
+    pub fn smaller_than_km(&self, km: f64) -> Vec<&NearEarthObject> {
+        self.near_earth_objects
+            .values()
+            .flatten()
+            .filter(|neo| {
+                neo.estimated_diameter
+                    .is_some_and(|diameter| diameter.kilometers.estimated_diameter_max < km)
+            })
+            .collect()
+    }
+
+    /// A GeoJSON `FeatureCollection` with one feature per object, describing
+    /// its closest approach in the feed.
+    ///
+    /// NeoWs reports distances, not positions, so every feature has a `null`
+    /// geometry, which GeoJSON allows for unlocated features. Objects without
+    /// close approach data are left out.
+    pub fn to_geojson(&self) -> serde_json::Value {
+        let features: Vec<_> = self
+            .near_earth_objects
+            .values()
+            .flatten()
+            .filter_map(|neo| {
+                let closest = neo.close_approach_data.iter().min_by(|a, b| {
+                    a.miss_distance
+                        .kilometers
+                        .total_cmp(&b.miss_distance.kilometers)
+                })?;
+                Some(serde_json::json!({
+                    "type": "Feature",
+                    "id": neo.id,
+                    "geometry": null,
+                    "properties": {
+                        "name": neo.name,
+                        "hazardous": neo.is_potentially_hazardous_asteroid,
+                        "close_approach_date": closest.close_approach_date.to_string(),
+                        "miss_distance_km": closest.miss_distance.kilometers,
+                        "velocity_km_s": closest.relative_velocity.kilometers_per_second,
+                    },
+                }))
+            })
+            .collect();
+
+        serde_json::json!({
+            "type": "FeatureCollection",
+            "features": features,
+        })
+    }
+}
+
+/// Iterates every object in the feed, day by day in date order.
+impl<'a> IntoIterator for &'a NeoWsResponse {
+    type Item = &'a NearEarthObject;
+    type IntoIter = std::iter::Flatten<btree_map::Values<'a, NaiveDate, Vec<NearEarthObject>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.near_earth_objects.values().flatten()
+    }
+}
+
+impl NeoWsError {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            NeoWsError::NeoWsApiError(err) => Some(err.code),
+            NeoWsError::NeoWsParamsError(_) => None,
+        }
+    }
+}
+
+impl NeoWsApi {
+    pub fn new(api_key: impl Into<ApiToken>, reqwest_client: reqwest::Client) -> Self {
+        let base_url = Url::parse(BASE_URL).expect("default NeoWs base url is valid");
+        Self::with_key_ring(KeyRing::new(api_key.into(), []), reqwest_client, base_url)
+    }
+
+    /// Like [`NeoWsApi::new`], but sends requests to `base_url` instead of
+    /// NASA, e.g. a mock server or a proxy.
+    pub fn with_base_url(
+        api_key: impl Into<ApiToken>,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> StellariaResult<Self> {
+        if base_url.cannot_be_a_base() {
+            return Err(url::ParseError::RelativeUrlWithCannotBeABaseBase.into());
+        }
+        Ok(Self::with_key_ring(
+            KeyRing::new(api_key.into(), []),
+            reqwest_client,
+            base_url,
+        ))
+    }
+
+    pub(crate) fn with_key_ring(
+        keys: KeyRing,
+        reqwest_client: reqwest::Client,
+        base_url: Url,
+    ) -> Self {
+        Self {
+            keys,
+            base_url,
+            reqwest_client,
+            clock: Clock::system(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// A params builder whose default `start_date` comes from this API's
+    /// clock.
+    pub fn params(&self) -> NeoWsParamsBuilder {
+        NeoWsParams::builder().today(self.clock.today())
+    }
+
+    /// Extra api_keys to rotate to when the active key gets a 429.
+    pub fn with_fallback_keys<T: Into<ApiToken>>(
+        mut self,
+        fallback_keys: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let fallback_keys = fallback_keys.into_iter().map(Into::into);
+        self.keys = KeyRing::new(self.keys.primary().clone(), fallback_keys);
+        self
+    }
+}
+
+impl Api for NeoWsApi {
+    type Params = NeoWsParams;
+    type Response = NeoWsResponse;
+
+    async fn get(&self, params: Self::Params) -> StellariaResult<Self::Response> {
+        request::get_json(
+            &self.reqwest_client,
+            &self.base_url,
+            &params,
+            Some(&self.keys),
+            ENDPOINT,
+            |code, body| NeoWsApiError {
+                code,
+                msg: match serde_json::from_slice::<NeoWsErrorBody>(body) {
+                    Ok(body) => body.error_message,
+                    Err(_) => request::error_message(body),
+                },
+            },
+        )
+        .await
+        .map_err(|err| err.with_context(ENDPOINT, params.describe()))
+    }
+}
+
+impl From<NeoWsApiError> for StellariaError {
+    fn from(err: NeoWsApiError) -> Self {
+        NeoWsError::NeoWsApiError(err).into()
+    }
+}
+
+impl From<NeoWsError> for StellariaError {
+    fn from(err: NeoWsError) -> Self {
+        ApiError::NeoWsError(err).into()
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::neows::NeoWsError::NeoWsParamsError;
+use crate::{Clock, StellariaError, StellariaResult, date_serde};
+
+/// The longest span, in days, NASA accepts between `start_date` and `end_date`.
+pub const MAX_FEED_DAYS: i64 = 7;
+
+/// Query for the NeoWs feed. The builder defaults `start_date` to today, by
+/// [`NeoWsApi::params`](crate::neows::NeoWsApi::params)'s clock if built from
+/// there; NASA defaults a missing `end_date` to seven days after
+/// `start_date`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NeoWsParams {
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "date_serde")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "date_serde")]
+    pub end_date: Option<NaiveDate>,
+}
+
+#[derive(Default, Debug)]
+pub struct NeoWsParamsBuilder {
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    today: Option<NaiveDate>,
+}
+
+impl NeoWsParamsBuilder {
+    pub fn start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn end_date(mut self, end_date: NaiveDate) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    pub(crate) fn today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    pub fn build(self) -> StellariaResult<NeoWsParams> {
+        match (self.start_date, self.end_date) {
+            (None, Some(_)) => Err(fail("end_date requires a start_date")),
+            (Some(start_date), Some(end_date)) if start_date > end_date => {
+                Err(fail("Start date cannot be greater than end date"))
+            }
+            (Some(start_date), Some(end_date))
+                if (end_date - start_date).num_days() > MAX_FEED_DAYS =>
+            {
+                Err(fail(&format!(
+                    "the feed spans at most {MAX_FEED_DAYS} days, got {start_date} to {end_date}"
+                )))
+            }
+            (start_date, end_date) => Ok(NeoWsParams {
+                start_date: Some(
+                    start_date
+                        .or(self.today)
+                        .unwrap_or_else(|| Clock::system().today()),
+                ),
+                end_date,
+            }),
+        }
+    }
+}
+
+fn fail(msg: &str) -> StellariaError {
+    NeoWsParamsError(msg.to_string()).into()
+}
+
+impl NeoWsParams {
+    pub fn builder() -> NeoWsParamsBuilder {
+        NeoWsParamsBuilder::default()
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        match (self.start_date, self.end_date) {
+            (Some(start_date), Some(end_date)) => format!("feed {start_date} to {end_date}"),
+            (Some(start_date), None) => format!("feed from {start_date}"),
+            _ => "today's feed".to_string(),
+        }
+    }
+}
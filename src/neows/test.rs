@@ -0,0 +1,343 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+#[cfg(test)]
+mod tests {
+    use crate::neows::{
+        NearEarthObject, NeoWsApi, NeoWsError, NeoWsParams, NeoWsResponse, OrbitingBody,
+    };
+    use crate::test_util::{TEST_KEY, mock_base_url};
+    use crate::{Api, ApiError, StellariaError};
+
+    use chrono::NaiveDate;
+    use reqwest::Client;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2015, 9, day).unwrap()
+    }
+
+    fn mock_neows(server: &MockServer) -> NeoWsApi {
+        NeoWsApi::with_base_url(
+            TEST_KEY,
+            Client::new(),
+            mock_base_url(server, "/neo/rest/v1/feed"),
+        )
+        .unwrap()
+    }
+
+    fn neo_json(id: &str, date: NaiveDate, hazardous: bool) -> serde_json::Value {
+        let diameter = |min: f64, max: f64| {
+            json!({
+                "estimated_diameter_min": min,
+                "estimated_diameter_max": max,
+            })
+        };
+        json!({
+            "links": {"self": format!("http://api.nasa.gov/neo/rest/v1/neo/{id}")},
+            "id": id,
+            "neo_reference_id": id,
+            "name": format!("({id})"),
+            "nasa_jpl_url": format!("https://ssd.jpl.nasa.gov/tools/sbdb_lookup.html#/?sstr={id}"),
+            "absolute_magnitude_h": 20.44,
+            "estimated_diameter": {
+                "kilometers": diameter(0.217, 0.485),
+                "meters": diameter(217.0, 485.3),
+                "miles": diameter(0.135, 0.302),
+                "feet": diameter(712.1, 1592.3),
+            },
+            "is_potentially_hazardous_asteroid": hazardous,
+            "close_approach_data": [{
+                "close_approach_date": date.to_string(),
+                "close_approach_date_full": "2015-Sep-08 20:28",
+                "epoch_date_close_approach": 1441744080000_i64,
+                "relative_velocity": {
+                    "kilometers_per_second": "18.1279360862",
+                    "kilometers_per_hour": "65260.5699103704",
+                    "miles_per_hour": "40550.3802312521",
+                },
+                "miss_distance": {
+                    "astronomical": "0.3027469457",
+                    "lunar": "117.7685618773",
+                    "kilometers": "45290298.225725659",
+                    "miles": "28142086.3515817342",
+                },
+                "orbiting_body": "Earth",
+            }],
+            "is_sentry_object": false,
+        })
+    }
+
+    // ==================== Params Tests ====================
+
+    #[test]
+    fn test_builder_accepts_seven_day_span() {
+        let params = NeoWsParams::builder()
+            .start_date(day(1))
+            .end_date(day(8))
+            .build()
+            .unwrap();
+
+        assert_eq!(params.start_date, Some(day(1)));
+        assert_eq!(params.end_date, Some(day(8)));
+    }
+
+    #[test]
+    fn test_builder_rejects_span_over_seven_days() {
+        let err = NeoWsParams::builder()
+            .start_date(day(1))
+            .end_date(day(9))
+            .build()
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            StellariaError::ApiError(ApiError::NeoWsError(NeoWsError::NeoWsParamsError(_)))
+        ));
+        assert!(err.is_client_error());
+    }
+
+    #[test]
+    fn test_builder_rejects_reversed_and_end_only() {
+        assert!(
+            NeoWsParams::builder()
+                .start_date(day(8))
+                .end_date(day(1))
+                .build()
+                .is_err()
+        );
+        assert!(NeoWsParams::builder().end_date(day(8)).build().is_err());
+        assert!(NeoWsParams::builder().build().is_ok());
+    }
+
+    // ==================== Diameter Tests ====================
+
+    #[test]
+    fn test_smaller_than_km_skips_missing_diameter() {
+        let mut unmeasured = neo_json("3553060", day(7), false);
+        unmeasured
+            .as_object_mut()
+            .unwrap()
+            .remove("estimated_diameter");
+        let feed: NeoWsResponse = serde_json::from_value(json!({
+            "element_count": 2,
+            "near_earth_objects": {
+                "2015-09-07": [neo_json("3426410", day(7), false), unmeasured],
+            },
+        }))
+        .unwrap();
+
+        let objects = &feed.near_earth_objects[&day(7)];
+        assert!(objects[0].estimated_diameter.is_some());
+        assert_eq!(objects[1].estimated_diameter, None);
+
+        let small: Vec<_> = feed
+            .smaller_than_km(1.0)
+            .iter()
+            .map(|neo| &neo.id)
+            .collect();
+        assert_eq!(small, ["3426410"]);
+        assert!(feed.smaller_than_km(0.1).is_empty());
+    }
+
+    #[test]
+    fn test_string_encoded_flags() {
+        let mut neo = neo_json("2465633", day(8), false);
+        neo["is_potentially_hazardous_asteroid"] = json!("true");
+        neo["is_sentry_object"] = json!("false");
+        let neo: NearEarthObject = serde_json::from_value(neo).unwrap();
+
+        assert!(neo.is_potentially_hazardous_asteroid);
+        assert!(!neo.is_sentry_object);
+    }
+
+    // ==================== Close Approach Tests ====================
+
+    #[test]
+    fn test_earth_approaches_skip_other_bodies() {
+        let mut neo = neo_json("2465633", day(8), true);
+        let mut venus = neo["close_approach_data"][0].clone();
+        venus["orbiting_body"] = json!("Venus");
+        let mut pluto = venus.clone();
+        pluto["orbiting_body"] = json!("Pluto");
+        neo["close_approach_data"]
+            .as_array_mut()
+            .unwrap()
+            .extend([venus, pluto]);
+        let neo: NearEarthObject = serde_json::from_value(neo).unwrap();
+
+        let bodies: Vec<_> = neo
+            .close_approach_data
+            .iter()
+            .map(|approach| &approach.orbiting_body)
+            .collect();
+        assert_eq!(
+            bodies,
+            [
+                &OrbitingBody::Earth,
+                &OrbitingBody::Venus,
+                &OrbitingBody::Other("Pluto".to_string()),
+            ]
+        );
+        let earth = neo.earth_approaches();
+        assert_eq!(earth.len(), 1);
+        assert_eq!(earth[0].orbiting_body, OrbitingBody::Earth);
+    }
+
+    #[test]
+    fn test_fastest_approach_picks_highest_velocity() {
+        let mut neo = neo_json("2465633", day(8), true);
+        let mut fast = neo["close_approach_data"][0].clone();
+        fast["close_approach_date"] = json!("2016-03-01");
+        fast["relative_velocity"] = json!({
+            "kilometers_per_second": "21.5",
+            "kilometers_per_hour": "77400.0",
+            "miles_per_hour": "48093.6",
+        });
+        let mut slow = neo["close_approach_data"][0].clone();
+        slow["relative_velocity"]["kilometers_per_second"] = json!("3.2");
+        neo["close_approach_data"]
+            .as_array_mut()
+            .unwrap()
+            .extend([fast, slow]);
+        let neo: NearEarthObject = serde_json::from_value(neo).unwrap();
+
+        let fastest = neo.fastest_approach().unwrap();
+        assert_eq!(
+            fastest.close_approach_date,
+            NaiveDate::from_ymd_opt(2016, 3, 1).unwrap()
+        );
+        assert_eq!(fastest.relative_velocity.kps(), 21.5);
+        assert_eq!(fastest.relative_velocity.kph(), 77400.0);
+        assert_eq!(fastest.relative_velocity.mph(), 48093.6);
+
+        let mut idle = neo_json("3553060", day(7), false);
+        idle["close_approach_data"] = json!([]);
+        let idle: NearEarthObject = serde_json::from_value(idle).unwrap();
+        assert!(idle.fastest_approach().is_none());
+    }
+
+    // ==================== GeoJSON Tests ====================
+
+    #[test]
+    fn test_to_geojson_emits_one_feature_per_object() {
+        let mut no_approaches = neo_json("3553060", day(7), false);
+        no_approaches["close_approach_data"] = json!([]);
+        let feed: NeoWsResponse = serde_json::from_value(json!({
+            "element_count": 3,
+            "near_earth_objects": {
+                "2015-09-07": [neo_json("3426410", day(7), false), no_approaches],
+                "2015-09-08": [neo_json("2465633", day(8), true)],
+            },
+        }))
+        .unwrap();
+
+        let geojson = feed.to_geojson();
+
+        assert_eq!(geojson["type"], "FeatureCollection");
+        let features = geojson["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        let hazardous = &features[1];
+        assert_eq!(hazardous["type"], "Feature");
+        assert!(hazardous["geometry"].is_null());
+        assert_eq!(hazardous["properties"]["name"], "(2465633)");
+        assert_eq!(hazardous["properties"]["hazardous"], true);
+        assert_eq!(
+            hazardous["properties"]["miss_distance_km"],
+            45290298.22572566
+        );
+        assert_eq!(hazardous["properties"]["velocity_km_s"], 18.1279360862);
+    }
+
+    // ==================== API Tests ====================
+
+    #[tokio::test]
+    async fn test_feed_deserializes_objects_by_date() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/neo/rest/v1/feed"))
+            .and(query_param("start_date", "2015-09-07"))
+            .and(query_param("end_date", "2015-09-08"))
+            .and(query_param("api_key", "TEST_KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "links": {
+                    "self": "http://api.nasa.gov/neo/rest/v1/feed?start_date=2015-09-07&end_date=2015-09-08&detailed=false&api_key=TEST_KEY",
+                },
+                "element_count": 3,
+                "near_earth_objects": {
+                    "2015-09-08": [neo_json("2465633", day(8), true)],
+                    "2015-09-07": [
+                        neo_json("3426410", day(7), false),
+                        neo_json("3553060", day(7), false),
+                    ],
+                },
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = NeoWsParams::builder()
+            .start_date(day(7))
+            .end_date(day(8))
+            .build()
+            .unwrap();
+        let feed: NeoWsResponse = mock_neows(&server).get(params).await.unwrap();
+
+        assert_eq!(feed.element_count, 3);
+        let self_link = feed.self_link.as_ref().unwrap();
+        assert_eq!(
+            self_link.as_str(),
+            "http://api.nasa.gov/neo/rest/v1/feed?start_date=2015-09-07&end_date=2015-09-08&detailed=false"
+        );
+        assert!(!self_link.as_str().contains(TEST_KEY));
+        assert_eq!(
+            feed.near_earth_objects.keys().copied().collect::<Vec<_>>(),
+            [day(7), day(8)]
+        );
+        let mut ids = Vec::new();
+        for neo in &feed {
+            ids.push(neo.id.as_str());
+        }
+        assert_eq!(ids, ["3426410", "3553060", "2465633"]);
+        assert_eq!(ids.len(), feed.element_count as usize);
+        let hazardous = &feed.near_earth_objects[&day(8)][0];
+        assert!(hazardous.is_potentially_hazardous_asteroid);
+        let approach = &hazardous.close_approach_data[0];
+        assert_eq!(approach.close_approach_date, day(8));
+        assert_eq!(approach.miss_distance.lunar, 117.7685618773);
+        assert_eq!(
+            approach.relative_velocity.kilometers_per_second,
+            18.1279360862
+        );
+    }
+
+    #[tokio::test]
+    async fn test_feed_error_message_is_surfaced() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/neo/rest/v1/feed"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "code": 400,
+                "http_error": "BAD_REQUEST",
+                "error_message": "Date Format Exception - Expected format (yyyy-mm-dd)",
+                "request": "http://api.nasa.gov/rest/v1/feed",
+            })))
+            .mount(&server)
+            .await;
+
+        let err = mock_neows(&server)
+            .get(NeoWsParams::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status(), Some(400));
+        assert_eq!(
+            err.to_string(),
+            "while fetching NeoWs for today's feed: \
+             http code 400: Date Format Exception - Expected format (yyyy-mm-dd)"
+        );
+    }
+}
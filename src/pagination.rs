@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::future::Future;
+
+use futures::stream::{self, Stream};
+
+use crate::StellariaResult;
+
+/// One page of a paginated endpoint.
+pub trait Page {
+    type Item;
+    /// Whatever fetches the following page: a page number, a next link, ...
+    type Request;
+
+    /// The request for the page after this one, or `None` on the last page.
+    fn next_request(&self) -> Option<Self::Request>;
+
+    fn items(self) -> Vec<Self::Item>;
+}
+
+/// Streams the items of `first_page` and every page after it, fetching each
+/// following page with `next_fn` only once the previous one is used up.
+///
+/// The stream ends after the first error.
+pub fn paginate<P, F, Fut>(
+    first_page: P,
+    next_fn: F,
+) -> impl Stream<Item = StellariaResult<P::Item>>
+where
+    P: Page,
+    F: FnMut(P::Request) -> Fut,
+    Fut: Future<Output = StellariaResult<P>>,
+{
+    let next = first_page.next_request();
+    let state = (first_page.items().into_iter(), next, next_fn);
+    stream::unfold(Some(state), |state| async move {
+        let (mut items, mut next, mut next_fn) = state?;
+        loop {
+            if let Some(item) = items.next() {
+                return Some((Ok(item), Some((items, next, next_fn))));
+            }
+            match next_fn(next.take()?).await {
+                Ok(page) => {
+                    next = page.next_request();
+                    items = page.items().into_iter();
+                }
+                Err(err) => return Some((Err(err), None)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StellariaError;
+
+    use futures::StreamExt;
+
+    struct NumberedPage {
+        page: u32,
+        last_page: u32,
+        items: Vec<&'static str>,
+    }
+
+    impl Page for NumberedPage {
+        type Item = &'static str;
+        type Request = u32;
+
+        fn next_request(&self) -> Option<u32> {
+            (self.page < self.last_page).then_some(self.page + 1)
+        }
+
+        fn items(self) -> Vec<&'static str> {
+            self.items
+        }
+    }
+
+    fn page(page: u32) -> NumberedPage {
+        let items = match page {
+            1 => vec!["a", "b"],
+            _ => vec!["c"],
+        };
+        NumberedPage {
+            page,
+            last_page: 2,
+            items,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paginate_follows_next_request() {
+        let mut requested = Vec::new();
+        let items: Vec<_> = paginate(page(1), |next| {
+            requested.push(next);
+            async move { Ok(page(next)) }
+        })
+        .collect()
+        .await;
+
+        let items: Vec<_> = items.into_iter().map(Result::unwrap).collect();
+        assert_eq!(items, ["a", "b", "c"]);
+        assert_eq!(requested, [2]);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_stops_after_error() {
+        let items: Vec<_> = paginate(page(1), |_| async {
+            Err::<NumberedPage, _>(StellariaError::ConfigError("page failed".to_string()))
+        })
+        .collect()
+        .await;
+
+        assert_eq!(items.len(), 3);
+        assert!(items[..2].iter().all(Result::is_ok));
+        assert!(matches!(items[2], Err(StellariaError::ConfigError(_))));
+    }
+}
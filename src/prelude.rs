@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Common imports for typical usage: `use stellaria::prelude::*;`.
+
+pub use crate::{
+    Api, ApiError, ApiToken, Clock, RateLimit, StellariaClient, StellariaClientBuilder,
+    StellariaError, StellariaResult,
+};
+
+#[cfg(feature = "apod")]
+pub use crate::apod::{
+    ApodError, ApodParams, ApodParamsBuilder, ApodResponse, ApodSummary, ImagePref, MediaType,
+    RetryPolicy, RetryStats, SkippedEntries,
+};
+
+#[cfg(feature = "epic")]
+pub use crate::epic::{EpicCollection, EpicError, EpicImage, EpicParams, EpicResponse};
+#[cfg(feature = "epic")]
+pub use crate::geo::LatLon;
+
+#[cfg(feature = "mars_photos")]
+pub use crate::mars_photos::{
+    MarsPhotosError, MarsPhotosParams, MarsPhotosResponse, Rover, RoverCamera,
+};
+
+#[cfg(feature = "neows")]
+pub use crate::neows::{NeoWsError, NeoWsParams, NeoWsParamsBuilder, NeoWsResponse};
+
+#[cfg(feature = "donki")]
+pub use crate::donki::{DonkiError, DonkiEvent, DonkiParams, DonkiResponse, DonkiTimelineEvent};
+
+#[cfg(feature = "eonet")]
+pub use crate::eonet::{
+    EonetCategoryId, EonetError, EonetEvent, EonetParams, EonetResponse, EonetStatus,
+};
+
+#[cfg(feature = "exoplanet")]
+pub use crate::exoplanet::{ExoplanetError, ExoplanetParams, ExoplanetResponse};
+
+#[cfg(feature = "insight")]
+pub use crate::insight::{InsightError, InsightParams, InsightResponse, SolWeather};
+
+#[cfg(feature = "image_library")]
+pub use crate::image_library::{
+    ImageLibraryError, LibraryItem, LibraryMediaType, SearchParams, SearchResponse,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prelude_brings_common_types_into_scope() {
+        let builder: StellariaClientBuilder = StellariaClient::builder("DEMO_KEY");
+        let _client: StellariaClient = builder.build().unwrap();
+        let _: fn(&StellariaError) -> bool = StellariaError::is_retryable;
+        let _: Option<&ApiError> = None;
+        let _: StellariaResult<()> = Ok(());
+    }
+
+    #[cfg(feature = "apod")]
+    #[test]
+    fn test_prelude_brings_apod_types_into_scope() {
+        fn assert_api<A: Api<Params = ApodParams, Response = Vec<ApodResponse>>>(_: &A) {}
+
+        let client = StellariaClient::new("DEMO_KEY");
+        let builder: ApodParamsBuilder = ApodParams::builder();
+        let _: fn(String) -> ApodError = ApodError::ApodParamsError;
+        assert!(builder.build().is_ok());
+        assert_api(&client.apod);
+    }
+}
@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! The request plumbing every endpoint shares: api_key rotation, telemetry,
+//! rate limit bookkeeping and turning failed responses into
+//! [`StellariaError`]s. Endpoint modules only describe their params, their
+//! response types and how to build their own error for a non-2xx status.
+//!
+//! `endpoint` is the name [`StellariaError::WithContext`] and the request
+//! metrics use for the endpoint, e.g. `"APOD"`.
+
+use std::fmt;
+use std::future::Future;
+use std::time::Instant;
+
+use bytes::{Bytes, BytesMut};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::keys::KeyRing;
+use crate::telemetry;
+use crate::{RateLimit, StellariaError, StellariaResult};
+
+/// How much of a non-JSON error body ends up in an error message.
+const MAX_ERROR_CHARS: usize = 1024;
+
+/// A response whose body has been read in full.
+pub(crate) struct Reply {
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Bytes,
+}
+
+/// NASA echoes the api_key in some headers and error bodies, so only the
+/// header names and the body's length are printed.
+impl fmt::Debug for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reply")
+            .field("status", &self.status)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("body_len", &self.body.len())
+            .finish()
+    }
+}
+
+/// Sends `request` and reads its body, recording the request's metrics.
+pub(crate) async fn fetch(
+    endpoint: &'static str,
+    request: reqwest::RequestBuilder,
+) -> StellariaResult<Reply> {
+    let started = Instant::now();
+    let resp = request.send().await;
+    telemetry::record_request(
+        endpoint,
+        resp.as_ref().ok().map(|resp| resp.status()),
+        started.elapsed(),
+    );
+    let mut resp = resp.map_err(request_error)?;
+    let status = resp.status();
+    let headers = resp.headers().clone();
+
+    // Read chunk by chunk so a connection that drops mid-body can report how
+    // much arrived.
+    let mut body = BytesMut::new();
+    loop {
+        match resp.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            Ok(None) => break,
+            Err(err) if err.is_body() || err.is_decode() => {
+                return Err(StellariaError::TruncatedResponse {
+                    bytes_read: body.len(),
+                });
+            }
+            Err(err) => return Err(request_error(err)),
+        }
+    }
+    Ok(Reply {
+        status,
+        headers,
+        body: body.freeze(),
+    })
+}
+
+/// Like [`fetch`], but without an async runtime.
+#[cfg(feature = "blocking")]
+pub(crate) fn fetch_blocking(
+    endpoint: &'static str,
+    request: reqwest::blocking::RequestBuilder,
+) -> StellariaResult<Reply> {
+    let started = Instant::now();
+    let resp = request.send();
+    telemetry::record_request(
+        endpoint,
+        resp.as_ref().ok().map(|resp| resp.status()),
+        started.elapsed(),
+    );
+    let mut resp = resp.map_err(request_error)?;
+    let status = resp.status();
+    let headers = resp.headers().clone();
+
+    let mut body = Vec::new();
+    if let Err(err) = std::io::Read::read_to_end(&mut resp, &mut body) {
+        // Failed reads come back as io errors wrapping reqwest's own.
+        let err = err
+            .into_inner()
+            .and_then(|err| err.downcast::<reqwest::Error>().ok());
+        return Err(match err {
+            Some(err) if !(err.is_body() || err.is_decode()) => request_error(*err),
+            _ => StellariaError::TruncatedResponse {
+                bytes_read: body.len(),
+            },
+        });
+    }
+    Ok(Reply {
+        status,
+        headers,
+        body: body.into(),
+    })
+}
+
+/// Wraps a failed request, keeping the api_key out of the url reqwest prints
+/// with the error.
+fn request_error(mut err: reqwest::Error) -> StellariaError {
+    if let Some(url) = err.url_mut() {
+        strip_api_key(url);
+    }
+    StellariaError::RequestError(err)
+}
+
+/// Removes any `api_key` query pair from `url`.
+pub(crate) fn strip_api_key(url: &mut Url) {
+    if !url.query_pairs().any(|(name, _)| name == "api_key") {
+        return;
+    }
+    let pairs: Vec<_> = url
+        .query_pairs()
+        .into_owned()
+        .filter(|(name, _)| name != "api_key")
+        .collect();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+}
+
+/// Calls `send` with the active key, moving on to the next untried key each
+/// time one is rate limited.
+pub(crate) async fn rotate_keys<T, F, Fut>(keys: &KeyRing, mut send: F) -> StellariaResult<T>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = StellariaResult<T>>,
+{
+    let mut tried = vec![false; keys.len()];
+    let mut index = keys.active();
+    loop {
+        tried[index] = true;
+        match send(index).await {
+            Err(err) if err.is_rate_limited() => match keys.rotate(index, &tried) {
+                Some(next) => index = next,
+                None => return Err(err),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Like [`rotate_keys`], but without an async runtime.
+#[cfg(feature = "blocking")]
+pub(crate) fn rotate_keys_blocking<T>(
+    keys: &KeyRing,
+    mut send: impl FnMut(usize) -> StellariaResult<T>,
+) -> StellariaResult<T> {
+    let mut tried = vec![false; keys.len()];
+    let mut index = keys.active();
+    loop {
+        tried[index] = true;
+        match send(index) {
+            Err(err) if err.is_rate_limited() => match keys.rotate(index, &tried) {
+                Some(next) => index = next,
+                None => return Err(err),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// GETs `url` with `query`, passing an api_key from `keys` for endpoints on
+/// `api.nasa.gov`, and returns the body of the first successful response.
+///
+/// A non-2xx status fails with `api_error(status, body)`.
+#[cfg_attr(
+    not(any(
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "donki",
+        feature = "eonet",
+        feature = "exoplanet",
+        feature = "insight",
+        feature = "image_library"
+    )),
+    allow(dead_code)
+)]
+pub(crate) async fn get_body<Q, E>(
+    client: &reqwest::Client,
+    url: &Url,
+    query: &Q,
+    keys: Option<&KeyRing>,
+    endpoint: &'static str,
+    api_error: impl Fn(u16, &[u8]) -> E,
+) -> StellariaResult<Bytes>
+where
+    Q: Serialize + ?Sized,
+    E: Into<StellariaError>,
+{
+    let send = |key_index: Option<usize>| {
+        let mut request = client.get(url.clone());
+        if let (Some(keys), Some(index)) = (keys, key_index) {
+            request = request.query(&[("api_key", keys.get(index).expose())]);
+        }
+        let request = request.query(query);
+        let api_error = &api_error;
+        async move {
+            let reply = fetch(endpoint, request).await?;
+            if let (Some(keys), Some(index)) = (keys, key_index) {
+                keys.record(index, RateLimit::from_headers(&reply.headers));
+            }
+            if !reply.status.is_success() {
+                return Err(api_error(reply.status.as_u16(), &reply.body).into());
+            }
+            Ok(reply.body)
+        }
+    };
+
+    match keys {
+        Some(keys) => rotate_keys(keys, |index| send(Some(index))).await,
+        None => send(None).await,
+    }
+}
+
+/// Like [`get_body`], but parses the body as JSON.
+#[cfg_attr(
+    not(any(
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "eonet",
+        feature = "exoplanet",
+        feature = "image_library"
+    )),
+    allow(dead_code)
+)]
+pub(crate) async fn get_json<T, Q, E>(
+    client: &reqwest::Client,
+    url: &Url,
+    query: &Q,
+    keys: Option<&KeyRing>,
+    endpoint: &'static str,
+    api_error: impl Fn(u16, &[u8]) -> E,
+) -> StellariaResult<T>
+where
+    T: DeserializeOwned,
+    Q: Serialize + ?Sized,
+    E: Into<StellariaError>,
+{
+    let body = get_body(client, url, query, keys, endpoint, api_error).await?;
+    parse_json(&body)
+}
+
+/// Parses a successful response body, telling a body that was cut short
+/// apart from one that's malformed.
+#[cfg_attr(
+    not(any(
+        feature = "apod",
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "eonet",
+        feature = "exoplanet",
+        feature = "insight",
+        feature = "image_library"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn parse_json<T: DeserializeOwned>(body: &[u8]) -> StellariaResult<T> {
+    serde_json::from_slice(body).map_err(|err| json_error(err, body.len()))
+}
+
+/// The error for a body of `bytes_read` bytes that failed to parse.
+pub(crate) fn json_error(err: serde_json::Error, bytes_read: usize) -> StellariaError {
+    if err.is_eof() {
+        StellariaError::TruncatedResponse { bytes_read }
+    } else {
+        StellariaError::JsonError(err)
+    }
+}
+
+/// The start of an error body, for endpoints that don't send structured
+/// errors.
+pub(crate) fn error_message(body: &[u8]) -> String {
+    String::from_utf8_lossy(body)
+        .chars()
+        .take(MAX_ERROR_CHARS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_reply_debug_omits_echoed_api_key() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-echo", HeaderValue::from_static("api_key=SECRET_KEY"));
+        let reply = Reply {
+            status: StatusCode::FORBIDDEN,
+            headers,
+            body: Bytes::from_static(br#"{"error": "bad key SECRET_KEY"}"#),
+        };
+
+        let debug = format!("{reply:?}");
+        assert!(!debug.contains("SECRET_KEY"), "{debug}");
+        assert!(debug.contains("x-echo"), "{debug}");
+    }
+}
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use serde::{Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StrOrNum {
+    Num(f64),
+    Str(String),
+}
+
+/// Deserializes a float that NASA may encode either as `1.23` or `"1.23"`.
+pub fn de_str_or_num_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match StrOrNum::deserialize(deserializer)? {
+        StrOrNum::Num(num) => Ok(num),
+        StrOrNum::Str(s) => s.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StrOrBool {
+    Bool(bool),
+    Str(String),
+}
+
+/// Deserializes a boolean that NASA may encode either as `true` or `"true"`.
+pub fn de_str_or_bool<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match StrOrBool::deserialize(deserializer)? {
+        StrOrBool::Bool(b) => Ok(b),
+        StrOrBool::Str(s) => match s.trim() {
+            s if s.eq_ignore_ascii_case("true") => Ok(true),
+            s if s.eq_ignore_ascii_case("false") => Ok(false),
+            s => Err(serde::de::Error::custom(format!(
+                "expected \"true\" or \"false\", got {s:?}"
+            ))),
+        },
+    }
+}
+
+/// Deserializes an optional string with surrounding whitespace trimmed,
+/// mapping a blank string to `None`.
+pub fn de_trimmed_opt_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = Option::<String>::deserialize(deserializer)?;
+    Ok(s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Distance {
+        #[serde(deserialize_with = "de_str_or_num_f64")]
+        km: f64,
+    }
+
+    #[test]
+    fn test_str_or_num_accepts_string() {
+        let distance: Distance = serde_json::from_str(r#"{"km": "1.23"}"#).unwrap();
+        assert_eq!(distance.km, 1.23);
+    }
+
+    #[test]
+    fn test_str_or_num_accepts_number() {
+        let distance: Distance = serde_json::from_str(r#"{"km": 1.23}"#).unwrap();
+        assert_eq!(distance.km, 1.23);
+    }
+
+    #[test]
+    fn test_str_or_num_rejects_garbage() {
+        let result = serde_json::from_str::<Distance>(r#"{"km": "far"}"#);
+        assert!(result.is_err());
+    }
+
+    #[derive(Deserialize)]
+    struct Flag {
+        #[serde(deserialize_with = "de_str_or_bool")]
+        set: bool,
+    }
+
+    #[test]
+    fn test_str_or_bool_accepts_both_forms() {
+        let flag: Flag = serde_json::from_str(r#"{"set": true}"#).unwrap();
+        assert!(flag.set);
+        let flag: Flag = serde_json::from_str(r#"{"set": "false"}"#).unwrap();
+        assert!(!flag.set);
+        let flag: Flag = serde_json::from_str(r#"{"set": " True "}"#).unwrap();
+        assert!(flag.set);
+        assert!(serde_json::from_str::<Flag>(r#"{"set": "yes"}"#).is_err());
+    }
+
+    #[derive(Deserialize)]
+    struct Credit {
+        #[serde(default, deserialize_with = "de_trimmed_opt_string")]
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_trimmed_opt_string() {
+        let credit: Credit = serde_json::from_str(r#"{"name": "\n  Jane  \n"}"#).unwrap();
+        assert_eq!(credit.name.as_deref(), Some("Jane"));
+
+        let credit: Credit = serde_json::from_str(r#"{"name": "  "}"#).unwrap();
+        assert_eq!(credit.name, None);
+
+        let credit: Credit = serde_json::from_str("{}").unwrap();
+        assert_eq!(credit.name, None);
+    }
+}
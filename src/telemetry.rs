@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Request metrics, emitted through the `metrics` crate when the `metrics`
+//! feature is enabled and compiled away otherwise:
+//!
+//! - `stellaria.requests.total`: counter labeled by `endpoint` and `status`
+//!   (the HTTP status, or `error` when no response arrived).
+//! - `stellaria.request.duration`: histogram of seconds until the response
+//!   headers arrived, labeled by `endpoint`.
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+#[cfg(feature = "metrics")]
+pub(crate) fn record_request(
+    endpoint: &'static str,
+    status: Option<StatusCode>,
+    elapsed: Duration,
+) {
+    let status = status.map_or_else(|| "error".to_string(), |status| status.as_u16().to_string());
+    metrics::counter!("stellaria.requests.total", "endpoint" => endpoint, "status" => status)
+        .increment(1);
+    metrics::histogram!("stellaria.request.duration", "endpoint" => endpoint)
+        .record(elapsed.as_secs_f64());
+}
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) fn record_request(
+    _endpoint: &'static str,
+    _status: Option<StatusCode>,
+    _elapsed: Duration,
+) {
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString,
+        Unit,
+    };
+    use wiremock::MockServer;
+
+    #[derive(Default)]
+    struct Samples(Mutex<Vec<f64>>);
+
+    impl HistogramFn for Samples {
+        fn record(&self, value: f64) {
+            self.0.lock().unwrap().push(value);
+        }
+    }
+
+    /// Keeps every counter and histogram, keyed by name and sorted labels.
+    #[derive(Default)]
+    struct TestRecorder {
+        counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+        histograms: Mutex<HashMap<String, Arc<Samples>>>,
+    }
+
+    fn key_string(key: &Key) -> String {
+        let mut labels: Vec<_> = key
+            .labels()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect();
+        labels.sort();
+        format!("{}{{{}}}", key.name(), labels.join(","))
+    }
+
+    impl TestRecorder {
+        fn counter(&self, key: &str) -> u64 {
+            self.counters
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or(0, |counter| counter.load(Ordering::Relaxed))
+        }
+
+        fn samples(&self, key: &str) -> usize {
+            self.histograms
+                .lock()
+                .unwrap()
+                .get(key)
+                .map_or(0, |samples| samples.0.lock().unwrap().len())
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let mut counters = self.counters.lock().unwrap();
+            Counter::from_arc(counters.entry(key_string(key)).or_default().clone())
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            let mut histograms = self.histograms.lock().unwrap();
+            Histogram::from_arc(histograms.entry(key_string(key)).or_default().clone())
+        }
+    }
+
+    /// Runs `requests` against a fresh mock server and returns everything
+    /// they recorded.
+    fn record<F, Fut>(requests: F) -> TestRecorder
+    where
+        F: FnOnce(MockServer) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let recorder = TestRecorder::default();
+        // The recorder is thread-local, so the requests must run on this thread.
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async { requests(MockServer::start().await).await })
+        });
+        recorder
+    }
+
+    #[cfg(feature = "apod")]
+    #[test]
+    fn test_apod_request_is_counted() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        use crate::Api;
+        use crate::apod::{ApodApi, ApodParams};
+        use crate::test_util::{TEST_KEY, mock_base_url};
+
+        let recorder = record(|server| async move {
+            Mock::given(method("GET"))
+                .and(path("/planetary/apod"))
+                .respond_with(ResponseTemplate::new(503))
+                .mount(&server)
+                .await;
+
+            let base_url = mock_base_url(&server, "/planetary/apod");
+            let apod = ApodApi::with_base_url(TEST_KEY, reqwest::Client::new(), base_url).unwrap();
+            let params = ApodParams::builder().build().unwrap();
+            apod.get(params).await.unwrap_err();
+        });
+
+        assert_eq!(
+            recorder.counter("stellaria.requests.total{endpoint=APOD,status=503}"),
+            1
+        );
+        assert_eq!(
+            recorder.samples("stellaria.request.duration{endpoint=APOD}"),
+            1
+        );
+    }
+
+    #[cfg(feature = "neows")]
+    #[test]
+    fn test_every_rotated_key_is_counted() {
+        use chrono::NaiveDate;
+        use serde_json::json;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, ResponseTemplate};
+
+        use crate::Api;
+        use crate::neows::{NeoWsApi, NeoWsParams};
+        use crate::test_util::mock_base_url;
+
+        let recorder = record(|server| async move {
+            Mock::given(method("GET"))
+                .and(path("/neo/rest/v1/feed"))
+                .and(query_param("api_key", "KEY_A"))
+                .respond_with(ResponseTemplate::new(429))
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/neo/rest/v1/feed"))
+                .and(query_param("api_key", "KEY_B"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({"element_count": 0, "near_earth_objects": {}})),
+                )
+                .mount(&server)
+                .await;
+
+            let base_url = mock_base_url(&server, "/neo/rest/v1/feed");
+            let neows = NeoWsApi::with_base_url("KEY_A", reqwest::Client::new(), base_url)
+                .unwrap()
+                .with_fallback_keys(["KEY_B"]);
+            let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+            let params = NeoWsParams::builder().start_date(date).build().unwrap();
+            neows.get(params).await.unwrap();
+        });
+
+        assert_eq!(
+            recorder.counter("stellaria.requests.total{endpoint=NeoWs,status=429}"),
+            1
+        );
+        assert_eq!(
+            recorder.counter("stellaria.requests.total{endpoint=NeoWs,status=200}"),
+            1
+        );
+        assert_eq!(
+            recorder.samples("stellaria.request.duration{endpoint=NeoWs}"),
+            2
+        );
+    }
+
+    #[cfg(feature = "eonet")]
+    #[test]
+    fn test_keyless_request_without_response_is_counted() {
+        use url::Url;
+
+        use crate::Api;
+        use crate::eonet::{EonetApi, EonetParams};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let recorder = record(|_| async move {
+            let base_url = Url::parse(&format!("http://{addr}/api/v3/events")).unwrap();
+            let eonet = EonetApi::with_base_url(reqwest::Client::new(), base_url).unwrap();
+            let params = EonetParams::builder().build().unwrap();
+            eonet.get(params).await.unwrap_err();
+        });
+
+        assert_eq!(
+            recorder.counter("stellaria.requests.total{endpoint=EONET,status=error}"),
+            1
+        );
+        assert_eq!(
+            recorder.samples("stellaria.request.duration{endpoint=EONET}"),
+            1
+        );
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Fixtures shared by the endpoint test modules.
+
+use url::Url;
+use wiremock::MockServer;
+
+/// The api_key endpoints under test are built with.
+#[cfg_attr(
+    not(any(
+        feature = "apod",
+        feature = "neows",
+        feature = "epic",
+        feature = "mars_photos",
+        feature = "donki",
+        feature = "insight"
+    )),
+    allow(dead_code)
+)]
+pub(crate) const TEST_KEY: &str = "TEST_KEY";
+
+/// `path` on `server`, as the base url of an endpoint under test.
+pub(crate) fn mock_base_url(server: &MockServer, path: &str) -> Url {
+    Url::parse(&format!("{}{path}", server.uri())).expect("mock server url is valid")
+}